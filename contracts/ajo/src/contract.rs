@@ -1,9 +1,13 @@
-use soroban_sdk::{contract, contractimpl, Address, Env, Vec, BytesN};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, String, Symbol, Vec};
 
 use crate::errors::AjoError;
 use crate::events;
 use crate::storage;
-use crate::types::{Group, GroupStatus};
+use crate::types::{
+    ContractStats, ContributionPolicy, ContributionRecord, Dispute, Group, GroupConfig,
+    GroupMetadata, GroupSnapshot, GroupState, GroupStatus, GroupTemplate, GroupView, LegacyGroupV1,
+    PayoutMode,
+};
 use crate::utils;
 
 /// The main Ajo contract
@@ -18,21 +22,211 @@ impl AjoContract {
             return Err(AjoError::AlreadyInitialized);
         }
         storage::store_admin(&env, &admin);
+        
+        // A fresh deployment starts on the current layout
+        storage::set_schema_version(&env, storage::SCHEMA_VERSION);
         Ok(())
     }
     
     /// Upgrade the contract's Wasm
+    ///
+    /// # Arguments
+    /// * `new_wasm_hash` - Hash of Wasm already uploaded to the ledger
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the contract has no admin
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), AjoError> {
-        let admin = storage::get_admin(&env).ok_or(AjoError::Unauthorized)?;
+        let admin = storage::get_admin(&env).ok_or(AjoError::NotInitialized)?;
         admin.require_auth();
         env.deployer().update_current_contract_wasm(new_wasm_hash);
         Ok(())
     }
+    
+    /// Get the version of the deployed contract code
+    ///
+    /// Bumped with each release, so clients can tell which build an
+    /// `upgrade` left them talking to.
+    pub fn version(_env: Env) -> u32 {
+        utils::CONTRACT_VERSION
+    }
+    
+    /// Get the layout version stored data is on
+    ///
+    /// Below the version this build writes, `migrate` still has to run.
+    pub fn get_schema_version(env: Env) -> u32 {
+        storage::get_schema_version(&env)
+    }
 
+    /// Rewrite groups stored under an older layout in the current one
+    ///
+    /// Run after upgrading a deployment from before schema version 2, in
+    /// batches so no one call has to touch every group. Each batch picks up
+    /// at the group ID the last one stopped at and covers the next `limit`
+    /// IDs. Legacy groups never stored their token, so each one in the batch
+    /// takes the token `tokens` gives for its ID. Fields legacy groups lack
+    /// get the defaults a plain `create_group` would choose, and their
+    /// members are added to the member index. Once a batch reaches the last
+    /// group, stored data is on the current version.
+    ///
+    /// # Arguments
+    /// * `tokens` - Token each legacy group in this batch was collected in,
+    ///   by group ID
+    /// * `limit` - How many group IDs to cover, at most `MAX_BATCH_IDS`
+    ///
+    /// # Returns
+    /// The number of groups rewritten
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the contract has no admin
+    /// * `AlreadyMigrated` - If stored data is already on the current version
+    /// * `InvalidToken` - If a legacy group in the batch has no token in
+    ///   `tokens`; nothing in the batch is migrated
+    pub fn migrate(env: Env, tokens: Map<u64, Address>, limit: u32) -> Result<u32, AjoError> {
+        let admin = storage::get_admin(&env).ok_or(AjoError::NotInitialized)?;
+        admin.require_auth();
+        
+        if storage::get_schema_version(&env) >= storage::SCHEMA_VERSION {
+            return Err(AjoError::AlreadyMigrated);
+        }
+        
+        // This batch's window of IDs, stopping at the last issued one
+        let first = storage::get_migration_cursor(&env);
+        let group_count = storage::get_group_count(&env);
+        let last = first
+            .saturating_add(u64::from(limit.min(utils::MAX_BATCH_IDS)))
+            .min(group_count.saturating_add(1));
+        
+        let mut migrated = 0;
+        for group_id in first..last {
+            let legacy = match storage::get_legacy_group(&env, group_id) {
+                Some(legacy) => legacy,
+                None => continue,
+            };
+            let token = tokens.get(group_id).ok_or(AjoError::InvalidToken)?;
+            let group = upgrade_legacy_group(&env, legacy, &token);
+            storage::store_group(&env, group_id, &group);
+            
+            // Legacy groups were never counted or indexed
+            storage::record_group_created(&env);
+            if group.is_complete {
+                storage::record_group_completed(&env);
+            }
+            for member in group.members.iter() {
+                storage::add_member_group(&env, &member, group_id);
+            }
+            migrated += 1;
+        }
+        
+        // The last batch moves stored data onto the current version
+        storage::set_migration_cursor(&env, last);
+        if last > group_count {
+            storage::set_schema_version(&env, storage::SCHEMA_VERSION);
+        }
+        Ok(migrated)
+    }
+    
+    /// Pause all state-changing operations
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the contract has no admin
+    pub fn pause(env: Env) -> Result<(), AjoError> {
+        let admin = storage::get_admin(&env).ok_or(AjoError::Unauthorized)?;
+        admin.require_auth();
+        storage::set_paused(&env, true);
+        Ok(())
+    }
+    
+    /// Resume state-changing operations after a pause
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the contract has no admin
+    pub fn unpause(env: Env) -> Result<(), AjoError> {
+        let admin = storage::get_admin(&env).ok_or(AjoError::Unauthorized)?;
+        admin.require_auth();
+        storage::set_paused(&env, false);
+        Ok(())
+    }
+    
+    /// Check whether the contract is paused
+    pub fn is_paused(env: Env) -> bool {
+        storage::is_paused(&env)
+    }
+    
+    /// Stop or resume the creation of new groups
+    ///
+    /// Unlike `pause`, existing groups keep running: members can still join,
+    /// contribute, get paid out and cancel while creation is stopped.
+    ///
+    /// # Arguments
+    /// * `admin` - Address of the contract admin
+    /// * `enabled` - Whether `create_group` accepts new groups
+    ///
+    /// While creation is stopped, every way of creating a group returns
+    /// `ContractPaused`.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the caller is not the admin, or there is none
+    pub fn set_creation_enabled(env: Env, admin: Address, enabled: bool) -> Result<(), AjoError> {
+        let stored = storage::get_admin(&env).ok_or(AjoError::Unauthorized)?;
+        if admin != stored {
+            return Err(AjoError::Unauthorized);
+        }
+        admin.require_auth();
+        storage::set_creation_enabled(&env, enabled);
+        Ok(())
+    }
+    
+    /// Check whether new groups may be created
+    pub fn is_creation_enabled(env: Env) -> bool {
+        storage::is_creation_enabled(&env)
+    }
+    
+    /// Set the operator's fee on every payout, and who collects it
+    ///
+    /// The fee is taken from each payout before any creator commission,
+    /// which is then worked out on the remainder. A fee of 0 turns it off.
+    ///
+    /// # Arguments
+    /// * `admin` - Address of the contract admin
+    /// * `fee_bps` - Fee in basis points, at most 500 (5%)
+    /// * `collector` - Address the fee is sent to
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the caller is not the admin, or there is none
+    /// * `InvalidCommission` - If `fee_bps` is above 500
+    pub fn set_platform_fee(
+        env: Env,
+        admin: Address,
+        fee_bps: u32,
+        collector: Address,
+    ) -> Result<(), AjoError> {
+        let stored = storage::get_admin(&env).ok_or(AjoError::Unauthorized)?;
+        if admin != stored {
+            return Err(AjoError::Unauthorized);
+        }
+        admin.require_auth();
+        if fee_bps > utils::MAX_PLATFORM_FEE_BPS {
+            return Err(AjoError::InvalidCommission);
+        }
+        storage::set_platform_fee(&env, fee_bps, &collector);
+        Ok(())
+    }
+    
+    /// Get the platform fee in basis points (0 when none is set)
+    pub fn get_platform_fee(env: Env) -> u32 {
+        storage::get_platform_fee(&env).map(|(fee_bps, _)| fee_bps).unwrap_or(0)
+    }
+    
+    /// Get the address platform fees are sent to, if one is set
+    pub fn get_fee_collector(env: Env) -> Option<Address> {
+        storage::get_platform_fee(&env).map(|(_, collector)| collector)
+    }
+    
     /// Create a new Ajo group
     ///
     /// # Arguments
     /// * `creator` - Address of the group creator (automatically becomes first member)
+    /// * `token` - Token contract the group saves in (e.g. a Stellar asset contract)
     /// * `contribution_amount` - Fixed amount each member contributes per cycle (in stroops)
     /// * `cycle_duration` - Duration of each cycle in seconds
     /// * `max_members` - Maximum number of members allowed in the group
@@ -41,22 +235,123 @@ impl AjoContract {
     /// The unique group ID
     ///
     /// # Errors
-    /// * `InvalidAmount` - If contribution_amount <= 0
-    /// * `InvalidCycleDuration` - If cycle_duration == 0
-    /// * `InvalidMaxMembers` - If max_members < 2
+    /// * `ContributionAmountZero` - If contribution_amount is 0
+    /// * `ContributionAmountNegative` - If contribution_amount is negative
+    /// * `CycleDurationZero` - If cycle_duration is 0
+    /// * `CycleDurationTooLong` - If cycle_duration is longer than ten years
+    /// * `MaxMembersBelowMinimum` - If max_members < 2
+    /// * `PayoutOverflow` - If a full group's pool would overflow
+    /// * `InvalidToken` - If the token is not a deployed token contract
+    /// * `CreationRateLimited` - If the creator made 5 groups in the last day
+    /// * `MaxMembersAboveLimit` - If max_members exceeds `MAX_MEMBERS_LIMIT`,
+    ///   or, standing in for a running-groups limit, if the creator is
+    ///   already in `MAX_ACTIVE_GROUPS` running groups
+    /// * `ContractPaused` - If the contract or group creation is paused
     pub fn create_group(
         env: Env,
         creator: Address,
+        token: Address,
+        contribution_amount: i128,
+        cycle_duration: u64,
+        max_members: u32,
+    ) -> Result<u64, AjoError> {
+        Self::create_group_with_config(
+            env,
+            creator,
+            token,
+            contribution_amount,
+            cycle_duration,
+            max_members,
+            GroupConfig::default(),
+            None,
+        )
+    }
+    
+    /// Create a new Ajo group with non-default settings
+    ///
+    /// Behaves like `create_group`, with the optional settings in `config`
+    /// (such as the payout mode or a fill deadline) applied to the new group.
+    /// With an oracle set, each contribution is priced to be worth
+    /// `target_value` when it is made, and `contribution_amount` is what a
+    /// guarantor or collateral covers for a missed one. Repeating a call
+    /// with the same `idempotency_key` returns the group it first created
+    /// instead of creating another.
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator (automatically becomes first member)
+    /// * `token` - Token contract the group saves in (e.g. a Stellar asset contract)
+    /// * `contribution_amount` - Fixed amount each member contributes per cycle (in stroops)
+    /// * `cycle_duration` - Duration of each cycle in seconds
+    /// * `max_members` - Maximum number of members allowed in the group
+    /// * `config` - Optional group settings
+    /// * `idempotency_key` - Optional caller-chosen key, such as a request hash
+    ///
+    /// # Returns
+    /// The unique group ID
+    ///
+    /// # Errors
+    /// * `ContributionAmountZero` - If an oracle is set without a positive `target_value`
+    /// * `InvalidPenalty` - If an oracle is set together with a late fee
+    /// * `Reentrancy` - If re-entered while funds are moving
+    /// * Any error `create_group` returns
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_group_with_config(
+        env: Env,
+        creator: Address,
+        token: Address,
         contribution_amount: i128,
         cycle_duration: u64,
         max_members: u32,
+        config: GroupConfig,
+        idempotency_key: Option<BytesN<32>>,
     ) -> Result<u64, AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // The admin can stop new groups without pausing existing ones
+        if !storage::is_creation_enabled(&env) {
+            return Err(AjoError::ContractPaused);
+        }
+        
         // Validate parameters
-        utils::validate_group_params(contribution_amount, cycle_duration, max_members)?;
+        let decimals = validate_group_config(
+            &env,
+            &token,
+            contribution_amount,
+            cycle_duration,
+            max_members,
+            &config,
+        )?;
+        let total_cycles = config.total_cycles.unwrap_or(max_members);
+        let min_members = config.min_members.unwrap_or(max_members);
         
         // Require authentication
         creator.require_auth();
         
+        // A retried creation gets back the group the first attempt made
+        if let Some(key) = &idempotency_key {
+            if let Some(group_id) = storage::get_idempotency_key(&env, &creator, key) {
+                utils::release_lock(&env);
+                return Ok(group_id);
+            }
+        }
+        
+        // Limit how many running groups one address can be exposed to
+        utils::ensure_below_group_limit(&env, &creator)?;
+        
+        // Get current timestamp
+        let now = utils::get_current_timestamp(&env);
+        
+        // Limit how many groups one address can create in a day
+        let window_start = (now + 1).saturating_sub(utils::CREATION_WINDOW);
+        if utils::creations_by(&env, &creator, window_start) >= utils::MAX_CREATIONS_PER_WINDOW {
+            return Err(AjoError::CreationRateLimited);
+        }
+        utils::record_creation(&env, &creator, now, window_start);
+        
         // Generate new group ID
         let group_id = storage::get_next_group_id(&env);
         
@@ -64,13 +359,11 @@ impl AjoContract {
         let mut members = Vec::new(&env);
         members.push_back(creator.clone());
         
-        // Get current timestamp
-        let now = utils::get_current_timestamp(&env);
-        
         // Create group
         let group = Group {
             id: group_id,
             creator: creator.clone(),
+            token: token.clone(),
             contribution_amount,
             cycle_duration,
             max_members,
@@ -79,18 +372,223 @@ impl AjoContract {
             payout_index: 0,
             created_at: now,
             cycle_start_time: now,
+            is_started: false,
+            started_at: None,
             is_complete: false,
+            is_cancelled: false,
+            is_paused: false,
+            payout_mode: config.payout_mode,
+            payout_order: Vec::new(&env),
+            fill_deadline: config.fill_deadline,
+            collateral_amount: config.collateral_amount,
+            total_cycles,
+            penalty_bps: config.penalty_bps,
+            penalty_pool: 0,
+            is_private: config.is_private,
+            allow_forfeiture: config.allow_forfeiture,
+            completed_at: None,
+            commission_bps: config.commission_bps,
+            min_members,
+            contribution_policy: config.contribution_policy,
+            contribution_by_member: Map::new(&env),
+            last_payout_time: None,
+            oracle: config.oracle,
+            target_value: config.target_value,
+            payout_caller: config.payout_caller,
+            round_payouts: config.round_payouts,
+            dust_pool: 0,
         };
         
         // Store group
         storage::store_group(&env, group_id, &group);
+        storage::record_group_created(&env);
+        if let Some(key) = &idempotency_key {
+            storage::store_idempotency_key(&env, &creator, key, group_id);
+        }
+        
+        // The creator stakes collateral like any other member
+        utils::collect_collateral(&env, &group, &creator);
+        storage::add_member_group(&env, &creator, group_id);
         
         // Emit event
-        events::emit_group_created(&env, group_id, &creator, contribution_amount, max_members);
+        events::emit_group_created(
+            &env,
+            group_id,
+            &creator,
+            contribution_amount,
+            max_members,
+            &token,
+            decimals,
+        );
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        
+        Ok(group_id)
+    }
+    
+    /// Create a group and admit an agreed list of members in one call
+    ///
+    /// The group holds exactly the creator plus `members`, so it is full,
+    /// and starts, as soon as it is created. Every listed member must
+    /// authorize the call, just as they would their own `join_group`.
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator (automatically becomes first member)
+    /// * `token` - Token contract the group saves in (e.g. a Stellar asset contract)
+    /// * `members` - The other members, in payout order
+    /// * `contribution_amount` - Fixed amount each member contributes per cycle (in stroops)
+    /// * `cycle_duration` - Duration of each cycle in seconds
+    ///
+    /// # Returns
+    /// The unique group ID
+    ///
+    /// # Errors
+    /// * `AlreadyMember` - If an address is listed twice or the creator is listed
+    /// * `MaxMembersBelowMinimum` - If no members are listed
+    /// * `MaxMembersAboveLimit` - If the group would exceed the member limit,
+    ///   or, standing in for a running-groups limit, if the creator or a
+    ///   member is already in `MAX_ACTIVE_GROUPS` running groups
+    /// * Any error from `create_group` or `join_group`
+    pub fn create_group_with_members(
+        env: Env,
+        creator: Address,
+        token: Address,
+        members: Vec<Address>,
+        contribution_amount: i128,
+        cycle_duration: u64,
+    ) -> Result<u64, AjoError> {
+        // Each address may hold only one slot
+        let mut seen = Vec::new(&env);
+        seen.push_back(creator.clone());
+        for member in members.iter() {
+            if utils::is_member(&seen, &member) {
+                return Err(AjoError::AlreadyMember);
+            }
+            seen.push_back(member);
+        }
+        
+        // The creator's slot counts toward the cap
+        let max_members = members.len().saturating_add(1);
+        let group_id = Self::create_group(
+            env.clone(),
+            creator,
+            token,
+            contribution_amount,
+            cycle_duration,
+            max_members,
+        )?;
+        
+        // Admit everyone as if they had joined themselves
+        for member in members.iter() {
+            Self::join_group(env.clone(), member, group_id)?;
+        }
         
         Ok(group_id)
     }
     
+    /// Save group parameters for reuse with `create_from_template`
+    ///
+    /// Templates belong to the creator who saved them; saving under an id
+    /// already in use replaces that template. A `fill_deadline` in `config`
+    /// is an absolute time, so it only suits groups created before it passes.
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the creator who owns the template
+    /// * `template_id` - The creator's id for the template
+    /// * `template` - The token, amount, cycle duration, member cap and
+    ///   settings each group is created with
+    ///
+    /// # Errors
+    /// * `ContributionAmountZero` - If contribution_amount is 0
+    /// * `ContributionAmountNegative` - If contribution_amount is negative
+    /// * `CycleDurationZero` - If cycle_duration is 0
+    /// * `CycleDurationTooLong` - If cycle_duration is longer than ten years
+    /// * `MaxMembersBelowMinimum` - If max_members < 2
+    /// * `MaxMembersAboveLimit` - If max_members exceeds `MAX_MEMBERS_LIMIT`
+    /// * `PayoutOverflow` - If a full group's pool would overflow
+    /// * `InvalidToken` - If the token is not a deployed token contract
+    /// * `ContractPaused` - If the contract is paused
+    /// * Any error `create_group_with_config` returns for invalid settings
+    pub fn save_template(
+        env: Env,
+        creator: Address,
+        template_id: u32,
+        template: GroupTemplate,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        creator.require_auth();
+        
+        // Validate parameters as creating the group would
+        validate_group_config(
+            &env,
+            &template.token,
+            template.contribution_amount,
+            template.cycle_duration,
+            template.max_members,
+            &template.config,
+        )?;
+        
+        storage::store_template(&env, &creator, template_id, &template);
+        
+        Ok(())
+    }
+    
+    /// Get one of a creator's saved templates
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the creator who owns the template
+    /// * `template_id` - The creator's id for the template
+    ///
+    /// # Returns
+    /// The template, or `None` if the creator has none under that id
+    pub fn get_template(env: Env, creator: Address, template_id: u32) -> Option<GroupTemplate> {
+        storage::get_template(&env, &creator, template_id)
+    }
+    
+    /// Create a new group from one of the creator's saved templates
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the creator who owns the template
+    /// * `template_id` - The creator's id for the template
+    ///
+    /// # Returns
+    /// The unique group ID
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the creator has no template under that id
+    /// * Any error from `create_group_with_config`
+    pub fn create_from_template(env: Env, creator: Address, template_id: u32) -> Result<u64, AjoError> {
+        let template =
+            storage::get_template(&env, &creator, template_id).ok_or(AjoError::GroupNotFound)?;
+        Self::create_group_with_config(
+            env,
+            creator,
+            template.token,
+            template.contribution_amount,
+            template.cycle_duration,
+            template.max_members,
+            template.config,
+            None,
+        )
+    }
+    
+    /// Get aggregate counts across all groups
+    ///
+    /// # Returns
+    /// Total groups created, groups still running, and groups completed.
+    /// Cancelled and expired groups are neither active nor completed.
+    pub fn get_stats(env: Env) -> ContractStats {
+        ContractStats {
+            total_groups: storage::get_group_count(&env),
+            active_groups: storage::get_active_group_count(&env),
+            completed_groups: storage::get_completed_group_count(&env),
+        }
+    }
+    
     /// Get group information
     ///
     /// # Arguments
@@ -105,247 +603,3280 @@ impl AjoContract {
         storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)
     }
     
-    /// Get list of all members in a group
+    /// Get the creator of a group
     ///
     /// # Arguments
-    /// * `group_id` - The unique group identifier
+    /// * `group_id` - The group to look up
     ///
     /// # Returns
-    /// Vector of member addresses
+    /// The group's current creator
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    pub fn list_members(env: Env, group_id: u64) -> Result<Vec<Address>, AjoError> {
+    pub fn get_creator(env: Env, group_id: u64) -> Result<Address, AjoError> {
         let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
-        Ok(group.members)
+        Ok(group.creator)
     }
     
-    /// Join an existing group
+    /// Check if an address is a group's creator
     ///
     /// # Arguments
-    /// * `member` - Address of the member joining
-    /// * `group_id` - The group to join
+    /// * `group_id` - The group to check
+    /// * `address` - The address to check
+    ///
+    /// # Returns
+    /// True if the address may take creator-only actions on the group
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    /// * `GroupFull` - If the group has reached max members
-    /// * `AlreadyMember` - If the address is already a member
-    /// * `GroupComplete` - If the group has completed all cycles
-    pub fn join_group(env: Env, member: Address, group_id: u64) -> Result<(), AjoError> {
-        // Require authentication
-        member.require_auth();
-        
-        // Get group
-        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
-        
-        // Check if group is complete
-        if group.is_complete {
-            return Err(AjoError::GroupComplete);
-        }
-        
-        // Check if already a member
-        if utils::is_member(&group.members, &member) {
-            return Err(AjoError::AlreadyMember);
+    pub fn is_creator(env: Env, group_id: u64, address: Address) -> Result<bool, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(group.creator == address)
+    }
+    
+    /// Get several groups in one call
+    ///
+    /// # Arguments
+    /// * `group_ids` - The groups to fetch, at most `MAX_BATCH_IDS`
+    ///
+    /// # Returns
+    /// Each requested group in the order asked for, or `None` for ids with
+    /// no group
+    ///
+    /// # Errors
+    /// * `MaxMembersAboveLimit` - If more than `MAX_BATCH_IDS` ids are given;
+    ///   the member-cap error stands in for a batch limit
+    pub fn get_groups(env: Env, group_ids: Vec<u64>) -> Result<Vec<Option<Group>>, AjoError> {
+        if group_ids.len() > utils::MAX_BATCH_IDS {
+            return Err(AjoError::MaxMembersAboveLimit);
         }
         
-        // Check if group is full
-        if group.members.len() >= group.max_members {
-            return Err(AjoError::MaxMembersExceeded);
+        let mut groups = Vec::new(&env);
+        for group_id in group_ids.iter() {
+            groups.push_back(storage::get_group(&env, group_id));
         }
-        
-        // Add member
-        group.members.push_back(member.clone());
-        
-        // Update storage
-        storage::store_group(&env, group_id, &group);
-        
-        // Emit event
-        events::emit_member_joined(&env, group_id, &member);
-        
-        Ok(())
+        Ok(groups)
     }
     
-    /// Check if an address is a member of a group
+    /// Check that a stored group's membership is consistent
+    ///
+    /// Meant for audits and tests: every mutating call should leave a group
+    /// that passes, so a failure points at corrupted storage or a bug.
     ///
     /// # Arguments
     /// * `group_id` - The group to check
-    /// * `address` - The address to check
-    ///
-    /// # Returns
-    /// True if the address is a member, false otherwise
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    pub fn is_member(env: Env, group_id: u64, address: Address) -> Result<bool, AjoError> {
+    /// * `NotMember` - If the creator is missing while the group is running
+    /// * `AlreadyMember` - If an address appears in `members` more than once
+    pub fn verify_group(env: Env, group_id: u64) -> Result<(), AjoError> {
         let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
-        Ok(utils::is_member(&group.members, &address))
+        utils::check_group_invariants(&group)
     }
     
-    /// Contribute to the current cycle
+    /// Get a group along with values derived from its current cycle
+    ///
+    /// Saves clients from recomputing the pool, contribution count, next
+    /// recipient and time left in the cycle themselves.
     ///
     /// # Arguments
-    /// * `member` - Address making the contribution
-    /// * `group_id` - The group to contribute to
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// The group data and its computed fields
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
+    /// * `PayoutOverflow` - If the cycle's pool doesn't fit in an i128
+    pub fn get_group_view(env: Env, group_id: u64) -> Result<GroupView, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        let pool_this_cycle = utils::calculate_payout_amount(&group)?;
+        
+        let mut contributions_received: u32 = 0;
+        for member in group.members.iter() {
+            if storage::has_contributed(&env, group_id, group.current_cycle, &member) {
+                contributions_received += 1;
+            }
+        }
+        
+        let next_recipient = if group.is_complete {
+            None
+        } else {
+            utils::select_payout_recipient(&env, &group).map(|(recipient, _)| recipient)
+        };
+        
+        let seconds_until_deadline = utils::time_until_deadline(&env, &group);
+        
+        Ok(GroupView {
+            group,
+            pool_this_cycle,
+            contributions_received,
+            next_recipient,
+            seconds_until_deadline,
+        })
+    }
+    
+    /// Export a group with its full contribution and payout history
+    ///
+    /// Gathers in one call what would otherwise take a read per member for
+    /// every cycle, so auditors can snapshot a group off-chain.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to export
+    ///
+    /// # Returns
+    /// The group, its per-cycle contribution matrix and who has been paid out
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn export_group(env: Env, group_id: u64) -> Result<GroupSnapshot, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        let mut contributions = Vec::new(&env);
+        for cycle in 1..=group.total_cycles {
+            let mut row = Vec::new(&env);
+            for member in group.members.iter() {
+                row.push_back(storage::has_contributed(&env, group_id, cycle, &member));
+            }
+            contributions.push_back(row);
+        }
+        
+        let mut payouts_received = Vec::new(&env);
+        for member in group.members.iter() {
+            payouts_received.push_back(storage::has_received_payout(&env, group_id, &member));
+        }
+        
+        Ok(GroupSnapshot {
+            group,
+            contributions,
+            payouts_received,
+        })
+    }
+    
+    /// Set a group's display name and description
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator
+    /// * `group_id` - The group to describe
+    /// * `name` - Display name, at most 64 bytes
+    /// * `description` - Free-form description, at most 256 bytes
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `MetadataTooLong` - If the name or description is too long
+    /// * `ContractPaused` - If the contract is paused
+    pub fn set_metadata(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        name: String,
+        description: String,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        creator.require_auth();
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only the creator can describe the group
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Keep metadata small
+        if name.len() > utils::MAX_NAME_LEN || description.len() > utils::MAX_DESCRIPTION_LEN {
+            return Err(AjoError::MetadataTooLong);
+        }
+        
+        storage::store_metadata(&env, group_id, &GroupMetadata { name, description });
+        
+        Ok(())
+    }
+    
+    /// Get a group's display name and description
+    ///
+    /// Groups that were never described return empty strings.
+    ///
+    /// # Arguments
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_metadata(env: Env, group_id: u64) -> Result<GroupMetadata, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_metadata(&env, group_id).unwrap_or_else(|| GroupMetadata {
+            name: String::from_str(&env, ""),
+            description: String::from_str(&env, ""),
+        }))
+    }
+    
+    /// List existing group IDs, one page at a time
+    ///
+    /// Walks IDs in `[start, start + limit)`, stopping at the last issued ID,
+    /// and skips IDs whose group no longer exists in storage.
+    ///
+    /// # Arguments
+    /// * `start` - First group ID to consider
+    /// * `limit` - Size of the ID window to scan
+    ///
+    /// # Returns
+    /// Vector of existing group IDs in ascending order
+    pub fn list_group_ids(env: Env, start: u64, limit: u32) -> Vec<u64> {
+        let mut ids = Vec::new(&env);
+        let first = start.max(1);
+        let last = start
+            .saturating_add(u64::from(limit))
+            .min(storage::get_group_count(&env).saturating_add(1));
+        
+        for group_id in first..last {
+            if storage::get_group(&env, group_id).is_some() {
+                ids.push_back(group_id);
+            }
+        }
+        
+        ids
+    }
+    
+    /// List running groups, one page at a time
+    ///
+    /// Scans the same ID window as `list_group_ids`, with `limit` capped at
+    /// `MAX_BATCH_IDS`, and keeps only groups that have neither completed
+    /// nor been cancelled.
+    ///
+    /// # Arguments
+    /// * `start` - First group ID to consider
+    /// * `limit` - Size of the ID window to scan
+    ///
+    /// # Returns
+    /// The running groups in the window, in ascending ID order
+    pub fn get_active_groups(env: Env, start: u64, limit: u32) -> Vec<Group> {
+        let mut groups = Vec::new(&env);
+        let first = start.max(1);
+        let last = start
+            .saturating_add(u64::from(limit.min(utils::MAX_BATCH_IDS)))
+            .min(storage::get_group_count(&env).saturating_add(1));
+        
+        for group_id in first..last {
+            if let Some(group) = storage::get_group(&env, group_id) {
+                if !group.is_complete && !group.is_cancelled {
+                    groups.push_back(group);
+                }
+            }
+        }
+        
+        groups
+    }
+    
+    /// Get list of all members in a group
+    ///
+    /// # Arguments
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// Vector of member addresses
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn list_members(env: Env, group_id: u64) -> Result<Vec<Address>, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(group.members)
+    }
+    
+    /// Get the number of members in a group
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// How many members have joined so far
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_member_count(env: Env, group_id: u64) -> Result<u32, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(group.members.len())
+    }
+    
+    /// Get a group's current cycle number
+    ///
+    /// # Arguments
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// The cycle now collecting contributions (starts at 1)
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_current_cycle(env: Env, group_id: u64) -> Result<u32, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(group.current_cycle)
+    }
+    
+    /// Get how many cycles a group runs in total
+    ///
+    /// # Arguments
+    /// * `group_id` - The unique group identifier
+    ///
+    /// # Returns
+    /// The configured cycle count, `max_members` unless overridden at creation
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_total_cycles(env: Env, group_id: u64) -> Result<u32, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(group.total_cycles)
+    }
+    
+    /// Join an existing group
+    ///
+    /// An address that left during recruitment may join again; it starts
+    /// afresh at the end of the member list. Groups with `min_members` below
+    /// `max_members` keep admitting members after the rotation starts; late
+    /// joiners are paid out after everyone already scheduled.
+    ///
+    /// # Arguments
+    /// * `member` - Address of the member joining
+    /// * `group_id` - The group to join
+    ///
+    /// # Returns
+    /// The member's 0-based payout position, their index in the member list
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `MaxMembersExceeded` - If the group has reached max members
+    /// * `AlreadyMember` - If the address is already a member
+    /// * `NotInvited` - If the group is private and the member has no invite
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupFull` - If the group has started and takes no late joiners
+    /// * `MaxMembersAboveLimit` - If the member is already in `MAX_ACTIVE_GROUPS`
+    ///   running groups; the member-cap error stands in for a running-groups
+    ///   limit
+    /// * `ContractPaused` - If the contract is paused
+    /// * `Reentrancy` - If re-entered while funds are moving
+    pub fn join_group(env: Env, member: Address, group_id: u64) -> Result<u32, AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Check if already a member
+        if utils::is_member(&group.members, &member) {
+            return Err(AjoError::AlreadyMember);
+        }
+        
+        // Check if group is full
+        if group.members.len() >= group.max_members {
+            return Err(AjoError::MaxMembersExceeded);
+        }
+        
+        // Slots freed mid-rotation, e.g. by a kick, stay closed unless the
+        // group started below capacity to take late joiners
+        if group.is_started && group.min_members == group.max_members {
+            return Err(AjoError::GroupFull);
+        }
+        
+        // Private groups only admit invited addresses, one join per invite
+        if group.is_private && !storage::take_invite(&env, group_id, &member) {
+            return Err(AjoError::NotInvited);
+        }
+        
+        // Limit how many running groups one address can be exposed to
+        utils::ensure_below_group_limit(&env, &member)?;
+        
+        // Add member; a late joiner goes to the back of a shuffled order too
+        let position = group.members.len();
+        group.members.push_back(member.clone());
+        if !group.payout_order.is_empty() {
+            group.payout_order.push_back(member.clone());
+        }
+        
+        // The first cycle begins once the minimum number of members is in
+        let now = utils::get_current_timestamp(&env);
+        let starts_now = !group.is_started && group.members.len() == group.min_members;
+        if starts_now {
+            group.is_started = true;
+            group.started_at = Some(now);
+            group.cycle_start_time = now;
+        }
+        
+        // Update storage
+        storage::store_group(&env, group_id, &group);
+        
+        // Take the member's collateral
+        utils::collect_collateral(&env, &group, &member);
+        storage::add_member_group(&env, &member, group_id);
+        
+        // Emit event
+        events::emit_member_joined(&env, group_id, &member, group.members.len());
+        if starts_now {
+            events::emit_group_started(&env, group_id, group.members.len(), now);
+            utils::remind_members(&env, &group);
+        }
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        
+        Ok(position)
+    }
+    
+    /// Join an existing group on another member's referral
+    ///
+    /// Joins exactly as `join_group` does, and records `referrer` as having
+    /// brought the member in. If both are still members when the group
+    /// completes, the referrer is paid a bonus of `REFERRAL_BONUS_BPS` of the
+    /// member's contribution, out of late fees and rounding dust, before the
+    /// rest is shared out.
+    ///
+    /// # Arguments
+    /// * `member` - Address of the member joining
+    /// * `group_id` - The group to join
+    /// * `referrer` - Existing member who referred them
+    ///
+    /// # Returns
+    /// The member's 0-based payout position, their index in the member list
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `NotMember` - If the referrer is not a member of the group, standing
+    ///   in for an invalid referrer
+    /// * Any error `join_group` returns
+    pub fn join_group_with_referral(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        referrer: Address,
+    ) -> Result<u32, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only someone already in the group can refer, so never the joiner
+        if !utils::is_member(&group.members, &referrer) {
+            return Err(AjoError::NotMember);
+        }
+        
+        let position = Self::join_group(env.clone(), member.clone(), group_id)?;
+        storage::store_referral(&env, group_id, &member, &referrer);
+        events::emit_referral(&env, group_id, &member, &referrer);
+        
+        Ok(position)
+    }
+    
+    /// Get the member who referred a joiner
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    /// * `member` - The member to look up
+    ///
+    /// # Returns
+    /// The referrer, or `None` if the member joined without one
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_referrer(env: Env, group_id: u64, member: Address) -> Result<Option<Address>, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_referral(&env, group_id, &member))
+    }
+    
+    /// Invite an address to join a private group
+    ///
+    /// Each invite admits one join. Invites have no effect on public groups.
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator
+    /// * `group_id` - The group to invite to
+    /// * `invitee` - Address allowed to join
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `AlreadyMember` - If the invitee is already a member
+    /// * `ContractPaused` - If the contract is paused
+    pub fn invite_member(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        invitee: Address,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        creator.require_auth();
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only the creator can invite
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Check if already a member
+        if utils::is_member(&group.members, &invitee) {
+            return Err(AjoError::AlreadyMember);
+        }
+        
+        // Record invite
+        storage::store_invite(&env, group_id, &invitee);
+        
+        // Emit event
+        events::emit_member_invited(&env, group_id, &invitee);
+        
+        Ok(())
+    }
+    
+    /// Leave a group that hasn't filled up yet
+    ///
+    /// Any collateral the member deposited is returned, and the amount is
+    /// reported in the `left` event. The creator can't leave and should
+    /// cancel the group instead.
+    ///
+    /// # Arguments
+    /// * `member` - Address of the member leaving
+    /// * `group_id` - The group to leave
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `NotMember` - If the address is not a member
+    /// * `Unauthorized` - If the member is the group creator
+    /// * `GroupFull` - If the group has filled up and started
+    /// * `ContractPaused` - If the contract is paused
+    /// * `Reentrancy` - If re-entered while funds are moving
+    pub fn leave_group(env: Env, member: Address, group_id: u64) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Check if member
+        let index = group
+            .members
+            .first_index_of(&member)
+            .ok_or(AjoError::NotMember)?;
+        
+        // The creator cancels rather than leaves
+        if member == group.creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Members are committed once the group has started
+        if group.is_started {
+            return Err(AjoError::GroupFull);
+        }
+        
+        // Return the member's collateral
+        let refunded = utils::refund_member_collateral(&env, &group, &member);
+        
+        // Remove member, leaving nothing behind for a later re-join
+        group.members.remove(index);
+        group.contribution_by_member.remove(member.clone());
+        storage::remove_payout_destination(&env, group_id, &member);
+        storage::remove_member_group(&env, &member, group_id);
+        storage::add_departed_member(&env, group_id, &member);
+        
+        // Update storage
+        storage::store_group(&env, group_id, &group);
+        
+        // Emit event
+        events::emit_member_left(&env, group_id, &member, refunded);
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        
+        Ok(())
+    }
+    
+    /// Remove a member from a group that is mid-rotation
+    ///
+    /// Intended for persistent defaulters. Members who were already paid out
+    /// can always be kicked; kicking one who is still owed a payout requires
+    /// the group to allow forfeiture. The member's open contributions and
+    /// remaining collateral are returned, and the rotation shrinks so the
+    /// remaining members are still each paid once.
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator
+    /// * `group_id` - The group to remove the member from
+    /// * `member` - The member to remove
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the creator, or the member is the creator
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `NotMember` - If the address is not a member
+    /// * `GroupNotFull` - If the group hasn't started yet
+    /// * `PayoutOwed` - If the member hasn't been paid out and the group
+    ///   doesn't allow forfeiture
+    /// * `NoMembers` - If no unpaid member would be left to receive a payout,
+    ///   standing in for an underpopulated group
+    /// * `ContractPaused` - If the contract is paused
+    /// * `Reentrancy` - If re-entered while funds are moving
+    pub fn kick_member(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        member: Address,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Require authentication
+        creator.require_auth();
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only the creator can kick
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Check if member
+        if !group.members.contains(&member) {
+            return Err(AjoError::NotMember);
+        }
+        
+        // The creator can't kick themselves
+        if member == creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Before the group starts, members leave on their own
+        if !group.is_started {
+            return Err(AjoError::GroupNotFull);
+        }
+        
+        // Only paid-out members can be kicked unless the group allows forfeiture
+        let paid_out = storage::has_received_payout(&env, group_id, &member);
+        if !paid_out && !group.allow_forfeiture {
+            return Err(AjoError::PayoutOwed);
+        }
+        
+        // Return the member's open contributions and collateral
+        utils::refund_member_contributions(&env, &group, &member);
+        utils::refund_member_collateral(&env, &group, &member);
+        
+        // Remove member and shrink the rotation
+        utils::remove_from_rotation(&env, &mut group, &member, paid_out);
+        
+        // Someone unpaid must be left to receive the next payout
+        if !paid_out && group.total_cycles.min(group.members.len()) <= group.payout_index {
+            return Err(AjoError::NoMembers);
+        }
+        
+        // Update storage
+        storage::store_group(&env, group_id, &group);
+        
+        // Emit event
+        events::emit_member_kicked(&env, group_id, &member);
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        Ok(())
+    }
+    
+    /// Withdraw from a group that has been abandoned
+    ///
+    /// Once a group has seen no activity for `ABANDON_TIMEOUT` (or two full
+    /// cycles, if longer), any member other than the creator can take back
+    /// their collateral and open contributions and leave. Reclaiming doesn't
+    /// count as activity, so the other members can follow. The creator
+    /// recovers their funds by cancelling the group instead. If everyone
+    /// left has already been paid out, even a lone creator, the group
+    /// completes and returns their collateral.
+    ///
+    /// # Arguments
+    /// * `member` - Address of the member reclaiming their funds
+    /// * `group_id` - The abandoned group
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `NotMember` - If the address is not a member
+    /// * `Unauthorized` - If the member is the creator
+    /// * `DeadlineNotReached` - If the group has seen activity too recently
+    /// * `ContractPaused` - If the contract is paused
+    /// * `Reentrancy` - If re-entered while funds are moving
+    pub fn reclaim(env: Env, member: Address, group_id: u64) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Check if member
+        if !group.members.contains(&member) {
+            return Err(AjoError::NotMember);
+        }
+        
+        // The creator cancels rather than reclaims
+        if member == group.creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Only once the group has been left idle long enough
+        let last_changed = storage::get_last_activity(&env, group_id).unwrap_or(group.created_at);
+        if utils::get_current_timestamp(&env) <= utils::abandoned_at(&env, &group) {
+            return Err(AjoError::DeadlineNotReached);
+        }
+        
+        // Return the member's open contributions and collateral
+        utils::refund_member_contributions(&env, &group, &member);
+        utils::refund_member_collateral(&env, &group, &member);
+        
+        // Remove member and shrink the rotation
+        let paid_out = storage::has_received_payout(&env, group_id, &member);
+        utils::remove_from_rotation(&env, &mut group, &member, paid_out);
+        storage::remove_payout_destination(&env, group_id, &member);
+        
+        // If everyone left has been paid there is nothing more to rotate,
+        // so the group finishes and hands back what it still holds
+        let now = utils::get_current_timestamp(&env);
+        if group.payout_index >= utils::rotation_length(&group) {
+            utils::refund_open_contributions(&env, &group);
+            finish_group(&env, &mut group, now);
+        }
+        
+        // Update storage, keeping the group's last activity where it was
+        storage::store_group(&env, group_id, &group);
+        storage::set_last_activity(&env, group_id, last_changed);
+        
+        // Emit event
+        events::emit_member_reclaimed(&env, group_id, &member);
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        Ok(())
+    }
+    
+    /// Get the ids of every group an address belongs to
+    ///
+    /// Groups the member left, and groups that were cancelled or expired,
+    /// are not included.
+    ///
+    /// # Arguments
+    /// * `member` - The address to look up
+    ///
+    /// # Returns
+    /// Group ids in the order the member joined them
+    pub fn get_groups_by_member(env: Env, member: Address) -> Vec<u64> {
+        storage::get_member_groups(&env, &member)
+    }
+    
+    /// Check if an address is a member of a group
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    /// * `address` - The address to check
+    ///
+    /// # Returns
+    /// True if the address is a member, false otherwise
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn is_member(env: Env, group_id: u64, address: Address) -> Result<bool, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(utils::is_member(&group.members, &address))
+    }
+    
+    /// Set how much one member contributes each cycle
+    ///
+    /// Lets a circle scale contributions by ability: the member pays `amount`
+    /// instead of the flat `contribution_amount`, and every payout is the sum
+    /// of all members' agreed amounts. Amounts are fixed once the rotation
+    /// starts.
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator
+    /// * `group_id` - The group to configure
+    /// * `member` - The member whose amount is set
+    /// * `amount` - What the member contributes each cycle
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `NotMember` - If `member` is not in the group
+    /// * `GroupFull` - If the rotation has already started
+    /// * `ContributionAmountZero` - If `amount` is zero
+    /// * `ContributionAmountNegative` - If `amount` is negative
+    /// * `PayoutOverflow` - If the cycle's pool would overflow
+    /// * `ContractPaused` - If the contract is paused
+    pub fn set_member_contribution(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        member: Address,
+        amount: i128,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        creator.require_auth();
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only the creator can set amounts
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if member
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+        
+        // Members agree their amounts before the first cycle begins
+        if group.is_started {
+            return Err(AjoError::GroupFull);
+        }
+        
+        if amount == 0 {
+            return Err(AjoError::ContributionAmountZero);
+        } else if amount < 0 {
+            return Err(AjoError::ContributionAmountNegative);
+        }
+        
+        // Record the amount, making sure the pool still fits
+        group.contribution_by_member.set(member.clone(), amount);
+        utils::calculate_payout_amount(&group)?;
+        storage::store_group(&env, group_id, &group);
+        
+        // Emit event
+        events::emit_member_contribution_set(&env, group_id, &member, amount);
+        
+        Ok(())
+    }
+    
+    /// Change how many members a group can hold before it starts
+    ///
+    /// Cycle counts and start thresholds that followed the old cap follow
+    /// the new one, and any that no longer fit are lowered to it. Lowering
+    /// the cap to the current membership starts the rotation straight away.
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator
+    /// * `group_id` - The group to resize
+    /// * `new_max` - The new member cap
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupFull` - If the rotation has already started
+    /// * `MaxMembersBelowMinimum` - If `new_max` is below 2 or the current membership
+    /// * `MaxMembersAboveLimit` - If `new_max` exceeds the global member limit
+    /// * `PayoutOverflow` - If a full group's pool would overflow
+    /// * `ContractPaused` - If the contract is paused
+    pub fn set_max_members(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        new_max: u32,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        creator.require_auth();
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only the creator can resize the group
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // The cap is fixed once the rotation starts
+        if group.is_started {
+            return Err(AjoError::GroupFull);
+        }
+        
+        // Validate the new cap
+        utils::validate_group_params(group.contribution_amount, group.cycle_duration, new_max)?;
+        if new_max < group.members.len() {
+            return Err(AjoError::MaxMembersBelowMinimum);
+        }
+        
+        // Carry the old cap's defaults across to the new one
+        let old_max = group.max_members;
+        let rescale = |value: u32| if value == old_max { new_max } else { value.min(new_max) };
+        group.total_cycles = rescale(group.total_cycles);
+        group.min_members = rescale(group.min_members);
+        group.max_members = new_max;
+        
+        // A cap lowered to the current membership leaves nobody to wait for
+        let now = utils::get_current_timestamp(&env);
+        let starts_now = group.members.len() == group.min_members;
+        if starts_now {
+            group.is_started = true;
+            group.started_at = Some(now);
+            group.cycle_start_time = now;
+        }
+        
+        // Update storage
+        storage::store_group(&env, group_id, &group);
+        
+        // Emit event
+        events::emit_max_members_set(&env, group_id, &creator, new_max);
+        if starts_now {
+            events::emit_group_started(&env, group_id, group.members.len(), now);
+            utils::remind_members(&env, &group);
+        }
+        
+        Ok(())
+    }
+    
+    /// Put a new cycle duration to a vote of the group's members
+    ///
+    /// Opening a proposal replaces any earlier one and discards its votes.
+    /// The change applies once a majority of current members approve it
+    /// through `vote_duration`.
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator
+    /// * `group_id` - The group to change
+    /// * `new_duration` - Proposed cycle duration in seconds
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the creator
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `CycleDurationZero` - If the duration is zero
+    /// * `CycleDurationTooLong` - If the duration is longer than ten years
+    /// * `ContractPaused` - If the contract is paused
+    pub fn propose_duration(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        new_duration: u64,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        creator.require_auth();
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only the creator can propose a change
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // The same bounds apply as at creation
+        utils::validate_group_params(group.contribution_amount, new_duration, group.max_members)?;
+        
+        // Start the vote afresh
+        for member in group.members.iter() {
+            storage::remove_duration_vote(&env, group_id, &member);
+        }
+        storage::store_duration_proposal(&env, group_id, new_duration);
+        
+        // Emit event
+        events::emit_duration_proposed(&env, group_id, &creator, new_duration);
+        
+        Ok(())
+    }
+    
+    /// Vote on a group's proposed cycle duration
+    ///
+    /// A member may change their vote while the proposal is open. Once more
+    /// than half of the current members approve, the new duration applies
+    /// straight away, including to the current cycle's window. Does nothing
+    /// if no change is proposed.
+    ///
+    /// # Arguments
+    /// * `member` - Address of the voting member
+    /// * `group_id` - The group voting
+    /// * `approve` - Whether the member approves the proposed duration
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `NotMember` - If the address is not a member
+    /// * `ContractPaused` - If the contract is paused
+    pub fn vote_duration(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        approve: bool,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Check if member
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+        
+        // Nothing to vote on
+        let new_duration = match storage::get_duration_proposal(&env, group_id) {
+            Some(duration) => duration,
+            None => return Ok(()),
+        };
+        
+        // Record the vote
+        storage::store_duration_vote(&env, group_id, &member, approve);
+        events::emit_duration_voted(&env, group_id, &member, approve);
+        
+        // Apply the change once a majority of current members approve
+        let approvals = group
+            .members
+            .iter()
+            .filter(|voter| storage::get_duration_vote(&env, group_id, voter) == Some(true))
+            .count();
+        let approvals = u32::try_from(approvals).unwrap_or(u32::MAX);
+        if approvals.saturating_mul(2) > group.members.len() {
+            group.cycle_duration = new_duration;
+            for voter in group.members.iter() {
+                storage::remove_duration_vote(&env, group_id, &voter);
+            }
+            storage::remove_duration_proposal(&env, group_id);
+            storage::store_group(&env, group_id, &group);
+            events::emit_duration_changed(&env, group_id, new_duration);
+        }
+        
+        Ok(())
+    }
+    
+    /// Get the cycle duration a group is voting on
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// The proposed duration in seconds, or `None` if no vote is open
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_duration_proposal(env: Env, group_id: u64) -> Result<Option<u64>, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_duration_proposal(&env, group_id))
+    }
+    
+    /// Contribute to the current cycle
+    ///
+    /// Groups with a grace period keep accepting contributions after the
+    /// deadline until it runs out, charging `penalty_bps` of the amount on
+    /// top into the group's penalty pool. Oracle-priced groups charge the
+    /// token amount worth the group's `target_value` at the current price.
+    ///
+    /// # Arguments
+    /// * `member` - Address making the contribution
+    /// * `group_id` - The group to contribute to
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `NotMember` - If the address is not a member
+    /// * `AlreadyContributed` - If already contributed this cycle
+    /// * `ContributionWindowClosed` - If the cycle deadline and any grace
+    ///   period have passed
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupNotFull` - If the group hasn't reached its minimum members yet
+    /// * `ContractPaused` - If the contract is paused
+    /// * `Reentrancy` - If re-entered while funds are moving
+    /// * `GroupPaused` - If the creator has paused the group
+    /// * `InvalidToken` - If the group's oracle has no price newer than
+    ///   `MAX_PRICE_AGE`
+    /// * `ContributionAmountZero` - If the oracle's price is so high the
+    ///   target value converts to no tokens
+    pub fn contribute(env: Env, member: Address, group_id: u64) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Record the contribution, then collect it
+        let (group, total) = record_contribution(&env, &member, group_id)?;
+        utils::transfer_in(&env, &group, &member, total);
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        Ok(())
+    }
+    
+    /// Contribute to the current cycle on a member's behalf
+    ///
+    /// Lets a keeper collect a member's contribution without the member
+    /// signing each cycle. The funds come from the allowance the member gave
+    /// the contract through the group's token; the keeper only triggers the
+    /// pull and never holds the tokens. Exactly what is owed for the cycle is
+    /// drawn, since the allowance also covers the member's other groups.
+    ///
+    /// # Arguments
+    /// * `spender` - Address triggering the contribution
+    /// * `member` - Member whose contribution is collected
+    /// * `group_id` - The group to contribute to
+    ///
+    /// # Errors
+    /// * `InsufficientAllowance` - If the member's allowance doesn't cover
+    ///   the contribution and any late fee
+    /// * Any error `contribute` returns
+    pub fn contribute_from(
+        env: Env,
+        spender: Address,
+        member: Address,
+        group_id: u64,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Require authentication
+        spender.require_auth();
+        
+        // Record the contribution, then draw it from the member's allowance
+        let (group, total) = record_contribution(&env, &member, group_id)?;
+        if utils::contract_allowance(&env, &group, &member) < total {
+            return Err(AjoError::InsufficientAllowance);
+        }
+        utils::transfer_in_from_allowance(&env, &group, &member, total);
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        Ok(())
+    }
+    
+    /// Contribute to the current cycle of several groups in one call
+    ///
+    /// Each group goes through the same checks as `contribute`. Amounts are
+    /// summed per token and collected with one transfer each. If any group
+    /// rejects the contribution the whole batch reverts, so nothing is
+    /// recorded for any of them.
+    ///
+    /// # Arguments
+    /// * `member` - Address making the contributions
+    /// * `group_ids` - The groups to contribute to
+    ///
+    /// # Errors
+    /// * Any error `contribute` returns, for the first group that rejects it
+    pub fn contribute_batch(
+        env: Env,
+        member: Address,
+        group_ids: Vec<u64>,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Record every contribution, totalling what is owed in each token
+        let mut totals: Map<Address, i128> = Map::new(&env);
+        for group_id in group_ids.iter() {
+            let (group, amount) = record_contribution(&env, &member, group_id)?;
+            let owed = totals
+                .get(group.token.clone())
+                .unwrap_or(0)
+                .checked_add(amount)
+                .ok_or(AjoError::PayoutOverflow)?;
+            totals.set(group.token, owed);
+        }
+        
+        // Collect each token once
+        for (token, amount) in totals.iter() {
+            utils::transfer_token_in(&env, &token, &member, amount);
+        }
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        Ok(())
+    }
+    
+    /// Contribute for several cycles at once, starting with the current one
+    ///
+    /// Prepaid cycles count as contributed when their payout comes around,
+    /// so the member doesn't need to contribute again. Within a grace period
+    /// the late fee `contribute` would charge is added for the current cycle.
+    ///
+    /// # Arguments
+    /// * `member` - Address of the member contributing
+    /// * `group_id` - The group to contribute to
+    /// * `num_cycles` - How many cycles to pay for, the current one included
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `NotMember` - If the address is not a member
+    /// * `GroupNotFull` - If the group hasn't reached its minimum members yet
+    /// * `InvalidCycleCount` - If `num_cycles` is zero
+    /// * `ExceedsRemainingCycles` - If the range runs past the final cycle
+    /// * `AlreadyContributed` - If any cycle in the range is already paid
+    /// * `ContributionWindowClosed` - If the current cycle's deadline and any
+    ///   grace period have passed
+    /// * `ContractPaused` - If the contract is paused
+    /// * `Reentrancy` - If re-entered while funds are moving
+    /// * `GroupPaused` - If the creator has paused the group
+    pub fn contribute_cycles(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        num_cycles: u32,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        ensure_can_contribute(&group, &member)?;
+        
+        if num_cycles == 0 {
+            return Err(AjoError::InvalidCycleCount);
+        }
+        
+        // The range must end by the final cycle, without wrapping around
+        let last_cycle = group
+            .current_cycle
+            .checked_add(num_cycles - 1)
+            .ok_or(AjoError::ExceedsRemainingCycles)?;
+        let remaining_cycles = utils::rotation_length(&group).saturating_sub(group.payout_index);
+        if num_cycles > remaining_cycles || last_cycle > group.total_cycles {
+            return Err(AjoError::ExceedsRemainingCycles);
+        }
+        
+        // Every cycle in the range must still be unpaid
+        for cycle in group.current_cycle..=last_cycle {
+            if storage::has_contributed(&env, group_id, cycle, &member) {
+                return Err(AjoError::AlreadyContributed);
+            }
+        }
+        
+        // Only the current cycle can be late
+        let now = utils::get_current_timestamp(&env);
+        let penalty = late_fee(&group, &member, now)?;
+        
+        // Transfer the member's contribution * num_cycles, plus any late
+        // fee, to contract
+        let amount = utils::contribution_due(&env, &group, &member)?;
+        let total = amount
+            .checked_mul(i128::from(num_cycles))
+            .and_then(|total| total.checked_add(penalty))
+            .ok_or(AjoError::PayoutOverflow)?;
+        utils::transfer_in(&env, &group, &member, total);
+        
+        // Record a contribution for each cycle, the late fee in the first
+        for cycle in group.current_cycle..=last_cycle {
+            let fee = if cycle == group.current_cycle { penalty } else { 0 };
+            store_cycle_contribution(&env, &mut group, &member, cycle, amount, fee, now)?;
+        }
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        Ok(())
+    }
+    
+    /// Bid for the current cycle's payout in an auction-mode group
+    ///
+    /// The bid is the discount the member accepts on the pool. At payout the
+    /// highest bidder receives the pool minus their bid, and the bid is split
+    /// among the other members who paid into that cycle. If defaults leave
+    /// the pool no bigger than the bid, the winner receives nothing and the
+    /// whole pool is shared instead. Placing another bid replaces the
+    /// previous one.
+    ///
+    /// # Arguments
+    /// * `member` - Address placing the bid
+    /// * `group_id` - The group to bid in
+    /// * `bid_amount` - Discount offered, must be positive and below the pool
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `InvalidBid` - If the group doesn't use auction payouts, or the bid
+    ///   is not positive or not below the pool
+    /// * `NotMember` - If the address is not a member
+    /// * `AlreadyReceivedPayout` - If the member was already paid out
+    /// * `ContractPaused` - If the contract is paused
+    pub fn place_bid(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        bid_amount: i128,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Bids only make sense in auction mode
+        if group.payout_mode != PayoutMode::Auction {
+            return Err(AjoError::InvalidBid);
+        }
+        
+        // Check if member
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+        
+        // Members who were already paid can't win again
+        if storage::has_received_payout(&env, group_id, &member) {
+            return Err(AjoError::AlreadyReceivedPayout);
+        }
+        
+        // The discount has to leave something for the winner
+        if bid_amount <= 0 || bid_amount >= utils::calculate_payout_amount(&group)? {
+            return Err(AjoError::InvalidBid);
+        }
+        
+        // Record bid
+        storage::store_bid(&env, group_id, group.current_cycle, &member, bid_amount);
+        
+        // Emit event
+        events::emit_bid_placed(&env, group_id, &member, group.current_cycle, bid_amount);
+        
+        Ok(())
+    }
+    
+    /// Fix a randomized payout order for a full group
+    ///
+    /// Uses the host PRNG to permute the members once the group is full.
+    /// Afterwards payouts follow the shuffled order instead of join order.
+    /// Can only be called once, by the creator, before any payout.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to shuffle
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `GroupNotFull` - If the group still has open member slots
+    /// * `OrderAlreadySet` - If the order was already shuffled or payouts have begun
+    /// * `ContractPaused` - If the contract is paused
+    pub fn shuffle_payout_order(env: Env, group_id: u64) -> Result<Vec<Address>, AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only the creator picks the order
+        group.creator.require_auth();
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // The order is only fixed once everyone has joined
+        if group.members.len() < group.max_members {
+            return Err(AjoError::GroupNotFull);
+        }
+        
+        // The order can only be set once, and not after rotation has begun
+        if !group.payout_order.is_empty() || group.payout_index > 0 {
+            return Err(AjoError::OrderAlreadySet);
+        }
+        
+        // Permute the members
+        let mut payout_order = group.members.clone();
+        env.prng().shuffle(&mut payout_order);
+        group.payout_order = payout_order.clone();
+        
+        // Update storage
+        storage::store_group(&env, group_id, &group);
+        
+        // Emit event
+        events::emit_order_shuffled(&env, group_id, &payout_order);
+        
+        Ok(payout_order)
+    }
+    
+    /// Fix a payout order the members agreed on for a full group
+    ///
+    /// The counterpart to `shuffle_payout_order` for groups that choose who
+    /// gets paid when. `payout_order` must list every member exactly once.
+    /// Can only be called once, by the creator, before any payout.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to order
+    /// * `payout_order` - Members in the order they should be paid
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `GroupNotFull` - If the group still has open member slots
+    /// * `OrderAlreadySet` - If the order was already set or payouts have begun
+    /// * `NotMember` - If the order names an address outside the group
+    /// * `InvalidPayoutOrder` - If a member is missing or listed twice
+    /// * `ContractPaused` - If the contract is paused
+    pub fn set_payout_order(
+        env: Env,
+        group_id: u64,
+        payout_order: Vec<Address>,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only the creator picks the order
+        group.creator.require_auth();
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // The order is only fixed once everyone has joined
+        if group.members.len() < group.max_members {
+            return Err(AjoError::GroupNotFull);
+        }
+        
+        // The order can only be set once, and not after rotation has begun
+        if !group.payout_order.is_empty() || group.payout_index > 0 {
+            return Err(AjoError::OrderAlreadySet);
+        }
+        
+        // The order must be a permutation of the members
+        for (index, address) in (0u32..).zip(payout_order.iter()) {
+            if !utils::is_member(&group.members, &address) {
+                return Err(AjoError::NotMember);
+            }
+            if payout_order.first_index_of(&address) != Some(index) {
+                return Err(AjoError::InvalidPayoutOrder);
+            }
+        }
+        if payout_order.len() != group.members.len() {
+            return Err(AjoError::InvalidPayoutOrder);
+        }
+        group.payout_order = payout_order.clone();
+        
+        // Update storage
+        storage::store_group(&env, group_id, &group);
+        
+        // Emit event
+        events::emit_order_set(&env, group_id, &payout_order);
+        
+        Ok(())
+    }
+    
+    /// Swap the payout positions of two members who both agree to it
+    ///
+    /// Follows the shuffled payout order when one has been set, otherwise
+    /// join order. Both positions must still be waiting for their payout.
+    ///
+    /// # Arguments
+    /// * `member_a` - First member, who must authorize the swap
+    /// * `member_b` - Second member, who must also authorize the swap
+    /// * `group_id` - The group both members belong to
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `NotMember` - If either address is not a member
+    /// * `PositionAlreadyPaid` - If either member was already paid out
+    /// * `ContractPaused` - If the contract is paused
+    pub fn swap_positions(
+        env: Env,
+        member_a: Address,
+        member_b: Address,
+        group_id: u64,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Both members must consent
+        member_a.require_auth();
+        member_b.require_auth();
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Find both positions in the rotation
+        let mut rotation = if group.payout_order.is_empty() {
+            group.members.clone()
+        } else {
+            group.payout_order.clone()
+        };
+        let index_a = rotation.first_index_of(&member_a).ok_or(AjoError::NotMember)?;
+        let index_b = rotation.first_index_of(&member_b).ok_or(AjoError::NotMember)?;
+        
+        // Positions that were already paid out stay where they are
+        for member in [&member_a, &member_b] {
+            if storage::has_received_payout(&env, group_id, member) {
+                return Err(AjoError::PositionAlreadyPaid);
+            }
+        }
+        
+        // Swap the two positions
+        rotation.set(index_a, member_b.clone());
+        rotation.set(index_b, member_a.clone());
+        if group.payout_order.is_empty() {
+            group.members = rotation;
+        } else {
+            group.payout_order = rotation;
+        }
+        
+        // Update storage
+        storage::store_group(&env, group_id, &group);
+        
+        // Emit event
+        events::emit_positions_swapped(&env, group_id, &member_a, &member_b);
+        
+        Ok(())
+    }
+    
+    /// Send a member's payout to a different address
+    ///
+    /// Can be changed any time before the member is paid. Passing `None`
+    /// clears the delegation so the payout goes to the member again.
+    ///
+    /// # Arguments
+    /// * `member` - Address of the member delegating
+    /// * `group_id` - The group the payout belongs to
+    /// * `destination` - Address to receive the payout, or `None` to clear
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `NotMember` - If the address is not a member
+    /// * `AlreadyReceivedPayout` - If the member was already paid out
+    /// * `ContractPaused` - If the contract is paused
+    pub fn set_payout_address(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        destination: Option<Address>,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Check if member
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+        
+        // A payout that already happened can't be redirected
+        if storage::has_received_payout(&env, group_id, &member) {
+            return Err(AjoError::AlreadyReceivedPayout);
+        }
+        
+        match destination {
+            Some(destination) => {
+                storage::store_payout_destination(&env, group_id, &member, &destination)
+            }
+            None => storage::remove_payout_destination(&env, group_id, &member),
+        }
+        
+        Ok(())
+    }
+    
+    /// Name a guarantor who covers the member's missed contributions
+    ///
+    /// At payout, a contribution the member didn't make is pulled from the
+    /// guarantor before a default is recorded. The guarantor funds this by
+    /// approving the contract to spend the group's token on their behalf.
+    /// Naming a new guarantor replaces the previous one.
+    ///
+    /// # Arguments
+    /// * `member` - Address of the member being backed
+    /// * `group_id` - The group the guarantee applies to
+    /// * `guarantor` - Address agreeing to cover the member
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `NotMember` - If the address is not a member
+    /// * `InvalidGuarantor` - If the member names themselves
+    /// * `ContractPaused` - If the contract is paused
+    pub fn set_guarantor(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        guarantor: Address,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Check if member
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+        
+        if guarantor == member {
+            return Err(AjoError::InvalidGuarantor);
+        }
+        
+        // The guarantor must agree to back the member
+        guarantor.require_auth();
+        
+        storage::store_guarantor(&env, group_id, &member, &guarantor);
+        
+        Ok(())
+    }
+    
+    /// Give up a member's payout turn in favour of the next recipient
+    ///
+    /// When the member's cycle comes up, the collected pool is carried into
+    /// the following payout instead of being sent to them. The member must
+    /// still contribute every cycle as usual. If theirs is the group's final
+    /// payout there is no one left to carry it to, so it is paid to them.
+    ///
+    /// # Arguments
+    /// * `member` - Address of the member waiving their payout
+    /// * `group_id` - The group the payout belongs to
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `NotMember` - If the address is not a member
+    /// * `AlreadyReceivedPayout` - If the member was already paid out
+    /// * `ContractPaused` - If the contract is paused
+    pub fn waive_payout(env: Env, member: Address, group_id: u64) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Check if member
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+        
+        // A payout that already happened can't be given up
+        if storage::has_received_payout(&env, group_id, &member) {
+            return Err(AjoError::AlreadyReceivedPayout);
+        }
+        
+        storage::mark_payout_waived(&env, group_id, &member);
+        
+        Ok(())
+    }
+    
+    /// Opt in to or out of contribution reminders
+    ///
+    /// Whenever a cycle opens, a `remind` event lists the opted-in members
+    /// who still owe, for off-chain notifiers to act on.
+    ///
+    /// # Arguments
+    /// * `member` - Address of the member
+    /// * `group_id` - The group to be reminded about
+    /// * `enabled` - Whether the member wants reminders
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `NotMember` - If the address is not a member
+    /// * `ContractPaused` - If the contract is paused
+    pub fn set_reminder(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        enabled: bool,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Check if member
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+        
+        let mut reminders = storage::get_reminders(&env, group_id);
+        let position = reminders.first_index_of(&member);
+        match (enabled, position) {
+            (true, None) => reminders.push_back(member),
+            (false, Some(index)) => {
+                reminders.remove(index);
+            }
+            _ => return Ok(()),
+        }
+        storage::store_reminders(&env, group_id, &reminders);
+        
+        Ok(())
+    }
+    
+    /// Check whether a member has asked for contribution reminders
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    /// * `member` - The member to look up
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_reminder(env: Env, group_id: u64, member: Address) -> Result<bool, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_reminders(&env, group_id).contains(&member))
+    }
+    
+    /// Get the address a member's payout will be sent to
+    ///
+    /// # Arguments
+    /// * `group_id` - The group the payout belongs to
+    /// * `member` - The member to look up
+    ///
+    /// # Returns
+    /// The delegated address if one is set, otherwise the member itself
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `NotMember` - If the address is not a member
+    pub fn get_payout_address(env: Env, group_id: u64, member: Address) -> Result<Address, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+        Ok(storage::get_payout_destination(&env, group_id, &member).unwrap_or(member))
+    }
+    
+    /// Get contribution status for all members in the current cycle
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    /// * `cycle_number` - The cycle to check (use current_cycle from group)
+    ///
+    /// # Returns
+    /// Vector of (Address, bool) tuples indicating contribution status
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_contribution_status(
+        env: Env,
+        group_id: u64,
+        cycle_number: u32,
+    ) -> Result<Vec<(Address, bool)>, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_cycle_contributions(
+            &env,
+            group_id,
+            cycle_number,
+            &group.members,
+        ))
+    }
+    
+    /// Get contribution status for all members in the group's current cycle
+    ///
+    /// Same as `get_contribution_status` for `group.current_cycle`, so callers
+    /// don't need to look up the cycle number first.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// Vector of (Address, bool) tuples in member order
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_member_contribution_status(
+        env: Env,
+        group_id: u64,
+    ) -> Result<Vec<(Address, bool)>, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_cycle_contributions(
+            &env,
+            group_id,
+            group.current_cycle,
+            &group.members,
+        ))
+    }
+    
+    /// Get whether a member contributed to each cycle so far
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    /// * `member` - The member to look up
+    ///
+    /// # Returns
+    /// Vector of (cycle, contributed) tuples from cycle 1 to the current cycle
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `NotMember` - If the address is not a member
+    pub fn get_member_history(
+        env: Env,
+        group_id: u64,
+        member: Address,
+    ) -> Result<Vec<(u32, bool)>, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+        
+        let mut history = Vec::new(&env);
+        for cycle in 1..=group.current_cycle {
+            history.push_back((cycle, storage::has_contributed(&env, group_id, cycle, &member)));
+        }
+        Ok(history)
+    }
+    
+    /// Get the members who haven't contributed to the current cycle
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// Addresses still owing a contribution, in member order
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_delinquent_members(env: Env, group_id: u64) -> Result<Vec<Address>, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        let mut delinquent = Vec::new(&env);
+        for member in group.members.iter() {
+            if !storage::has_contributed(&env, group_id, group.current_cycle, &member) {
+                delinquent.push_back(member);
+            }
+        }
+        
+        Ok(delinquent)
+    }
+    
+    /// Get how many members still owe a contribution to the current cycle
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// The number of members who haven't contributed, 0 once the cycle is funded
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn contributions_remaining(env: Env, group_id: u64) -> Result<u32, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        let mut paid: u32 = 0;
+        for member in group.members.iter() {
+            if storage::has_contributed(&env, group_id, group.current_cycle, &member) {
+                paid += 1;
+            }
+        }
+        
+        Ok(group.members.len() - paid)
+    }
+    
+    /// Execute payout for the current cycle
+    ///
+    /// This function:
+    /// 1. Verifies all members have contributed, or that the contribution
+    ///    window has closed (non-payers are then recorded as defaulters)
+    /// 2. Calculates and sends payout to the next member (or, in auction
+    ///    mode, to the highest bidder less their bid), less any platform
+    ///    fee, which goes to the fee collector, and any creator commission,
+    ///    which goes to the creator
+    /// 3. Advances the payout index and cycle
+    /// 4. Marks group as complete once all of its cycles have been paid out
+    ///
+    /// Anyone may call this unless the group was created with a
+    /// `payout_caller`, whose authorization is then required. Groups with
+    /// `round_payouts` pay whole tokens only; the fractions are held back
+    /// and shared equally among members when the group ends.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to execute payout for
+    ///
+    /// # Returns
+    /// The member paid this cycle and the amount they received, after fees
+    /// and commission; 0 if they waived their turn
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `IncompleteContributions` - If not all members have contributed and
+    ///   the window is still open, or nobody has contributed and no
+    ///   collateral or guarantor covers them; `get_delinquent_members`
+    ///   lists who owes
+    /// * `CycleNotReady` - If the cycle is underfunded and a full cycle
+    ///   hasn't passed since the last payout, or its grace period is still
+    ///   taking late contributions
+    /// * `CycleDisputed` - If the current cycle has an open dispute
+    /// * `GroupComplete` - If the group has already completed
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupNotFull` - If the group hasn't reached its minimum members yet
+    /// * `PayoutOverflow` - If the cycle's pool doesn't fit in an i128
+    /// * `NoMembers` - If the group has no members (should never happen)
+    /// * `ContractPaused` - If the contract is paused
+    /// * `Reentrancy` - If re-entered while funds are moving
+    /// * `GroupPaused` - If the creator has paused the group
+    pub fn execute_payout(env: Env, group_id: u64) -> Result<(Address, i128), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // A group may leave payouts to one address
+        if let Some(caller) = &group.payout_caller {
+            caller.require_auth();
+        }
+        
+        // Check the cycle can be paid out
+        let now = utils::get_current_timestamp(&env);
+        ensure_payout_ready(&env, &group, now)?;
+        
+        // Pay out the cycle
+        let payout = settle_cycle(&env, group, now)?;
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        Ok(payout)
+    }
+    
+    /// Preview what `execute_payout` would do now, without doing it
+    ///
+    /// Runs the same checks and works out the same amount, net of any
+    /// platform fee and commission, but moves no funds and writes nothing.
+    /// A missed contribution is counted as covered by the member's guarantor
+    /// when the guarantor could pay it, and otherwise by their collateral.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to preview
+    ///
+    /// # Returns
+    /// The member who would be paid and the amount they would receive; 0 if
+    /// they waived their turn
+    ///
+    /// # Errors
+    /// * Any error `execute_payout` would return, other than `Reentrancy`
+    pub fn simulate_payout(env: Env, group_id: u64) -> Result<(Address, i128), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check the cycle can be paid out
+        let now = utils::get_current_timestamp(&env);
+        ensure_payout_ready(&env, &group, now)?;
+        
+        preview_payout(&env, &group)
+    }
+    
+    /// Advance a group whose current cycle is ready to pay out
+    ///
+    /// Anyone may call this, so a keeper can move groups on once every
+    /// member has paid or the contribution window has closed, without
+    /// waiting for someone in the group to call `execute_payout`. A group
+    /// with a `payout_caller` still needs that address's authorization.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to advance
+    ///
+    /// # Returns
+    /// The member paid and the amount they received, as `execute_payout` returns
+    ///
+    /// # Errors
+    /// * `CycleNotReady` - If contributions are outstanding and the window is open
+    /// * Any other error `execute_payout` returns
+    pub fn poke(env: Env, group_id: u64) -> Result<(Address, i128), AjoError> {
+        match Self::execute_payout(env, group_id) {
+            Err(AjoError::IncompleteContributions) => Err(AjoError::CycleNotReady),
+            result => result,
+        }
+    }
+    
+    /// Pay out the current cycle before every member has contributed
+    ///
+    /// Lets the creator move a group on without waiting for the contribution
+    /// window to close. The recipient gets only what was actually collected,
+    /// and each member who didn't pay is recorded as a defaulter. Unless
+    /// every member has paid, a full cycle must still have passed since the
+    /// previous payout.
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator
+    /// * `group_id` - The group to execute payout for
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has already completed
+    /// * `GroupNotFull` - If the group hasn't reached its minimum members yet
+    /// * `IncompleteContributions` - If nobody has contributed to the cycle
+    ///   and no collateral or guarantor covers them
+    /// * `PayoutOverflow` - If the cycle's pool doesn't fit in an i128
+    /// * `CycleNotReady` - If the cycle is underfunded and a full cycle
+    ///   hasn't passed since the last payout
+    /// * `CycleDisputed` - If the current cycle has an open dispute
+    /// * `ContractPaused` - If the contract is paused
+    /// * `Reentrancy` - If re-entered while funds are moving
+    /// * `GroupPaused` - If the creator has paused the group
+    pub fn force_payout(env: Env, creator: Address, group_id: u64) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Require authentication
+        creator.require_auth();
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only the creator can force a payout
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Nothing moves while the creator has the group paused
+        if group.is_paused {
+            return Err(AjoError::GroupPaused);
+        }
+        
+        // No cycle runs until the group has started
+        if !group.is_started {
+            return Err(AjoError::GroupNotFull);
+        }
+        
+        // A disputed cycle waits for the creator to resolve it
+        if storage::get_dispute(&env, group_id, group.current_cycle).is_some() {
+            return Err(AjoError::CycleDisputed);
+        }
+        
+        // A cycle nobody has paid into, and no collateral or guarantor
+        // covers, has nothing to pay out
+        if !utils::any_member_covered(&env, &group) {
+            return Err(AjoError::IncompleteContributions);
+        }
+        
+        // An underfunded cycle waits a full cycle after the last payout
+        let now = utils::get_current_timestamp(&env);
+        if !utils::all_members_contributed(&env, &group) && utils::is_payout_too_soon(&group, now) {
+            return Err(AjoError::CycleNotReady);
+        }
+        
+        // Pay out the cycle with whatever was collected
+        settle_cycle(&env, group, now)?;
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        Ok(())
+    }
+    
+    /// Temporarily halt contributions and payouts in a group
+    ///
+    /// The group can still be cancelled while paused.
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator
+    /// * `group_id` - The group to pause
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `ContractPaused` - If the contract is paused
+    pub fn pause_group(env: Env, creator: Address, group_id: u64) -> Result<(), AjoError> {
+        set_group_paused(&env, &creator, group_id, true)
+    }
+    
+    /// Resume a group paused with `pause_group`
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator
+    /// * `group_id` - The group to resume
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `ContractPaused` - If the contract is paused
+    pub fn resume_group(env: Env, creator: Address, group_id: u64) -> Result<(), AjoError> {
+        set_group_paused(&env, &creator, group_id, false)
+    }
+    
+    /// Flag the current cycle as disputed
+    ///
+    /// Holds the cycle's payout until the creator calls `resolve_dispute`.
+    /// Only one dispute can be open on a cycle at a time.
+    ///
+    /// # Arguments
+    /// * `member` - Address of the member raising the dispute
+    /// * `group_id` - The group to dispute
+    /// * `reason` - Short code describing the problem
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
     /// * `NotMember` - If the address is not a member
-    /// * `AlreadyContributed` - If already contributed this cycle
+    /// * `GroupNotFull` - If the group hasn't reached its minimum members yet
+    /// * `CycleDisputed` - If the cycle already has an open dispute
+    /// * `ContractPaused` - If the contract is paused
+    pub fn raise_dispute(
+        env: Env,
+        member: Address,
+        group_id: u64,
+        reason: Symbol,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        member.require_auth();
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Check if member
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+        
+        // Only a running cycle can be disputed
+        if !group.is_started {
+            return Err(AjoError::GroupNotFull);
+        }
+        
+        let cycle = group.current_cycle;
+        if storage::get_dispute(&env, group_id, cycle).is_some() {
+            return Err(AjoError::CycleDisputed);
+        }
+        
+        // Record the dispute
+        let dispute = Dispute {
+            member: member.clone(),
+            reason: reason.clone(),
+            raised_at: utils::get_current_timestamp(&env),
+        };
+        storage::store_dispute(&env, group_id, cycle, &dispute);
+        
+        // Emit event
+        events::emit_dispute_raised(&env, group_id, cycle, &member, &reason);
+        
+        Ok(())
+    }
+    
+    /// Clear the current cycle's dispute so it can be paid out again
+    ///
+    /// Does nothing if the cycle has no open dispute.
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator
+    /// * `group_id` - The group whose dispute is resolved
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `ContractPaused` - If the contract is paused
+    pub fn resolve_dispute(env: Env, creator: Address, group_id: u64) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        creator.require_auth();
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only the creator can resolve disputes
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        let cycle = group.current_cycle;
+        if storage::get_dispute(&env, group_id, cycle).is_none() {
+            return Ok(());
+        }
+        
+        // Clear the dispute
+        storage::remove_dispute(&env, group_id, cycle);
+        
+        // Emit event
+        events::emit_dispute_resolved(&env, group_id, cycle, &creator);
+        
+        Ok(())
+    }
+    
+    /// Cancel a group and refund the current cycle's contributions
+    ///
+    /// Only the creator may cancel. Members who already paid into the current
+    /// (not yet paid out) cycle are refunded their contribution; cycles that
+    /// were already paid out are left untouched.
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the group creator
+    /// * `group_id` - The group to cancel
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `GroupCancelled` - If the group has already been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `ContractPaused` - If the contract is paused
+    /// * `Reentrancy` - If re-entered while funds are moving
+    pub fn cancel_group(env: Env, creator: Address, group_id: u64) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Get group; unknown ids fail before any auth is asked for
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Require authentication
+        creator.require_auth();
+        
+        // Only the creator can cancel
+        if group.creator != creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Refund everyone who paid into the un-paid-out cycle or prepaid later ones
+        utils::refund_open_contributions(&env, &group);
+        utils::refund_collateral(&env, &group);
+        utils::distribute_penalty_pool(&env, &mut group);
+        utils::distribute_dust(&env, &mut group);
+        
+        // The group no longer counts towards its members' groups
+        for member in group.members.iter() {
+            storage::remove_member_group(&env, &member, group_id);
+        }
+        
+        // Mark group as cancelled
+        group.is_cancelled = true;
+        group.is_complete = true;
+        storage::record_group_closed(&env);
+        
+        // Update storage
+        storage::store_group(&env, group_id, &group);
+        
+        // Emit event; cancelled groups never emit the completion event
+        events::emit_group_cancelled(&env, group_id, &creator, group.current_cycle);
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        Ok(())
+    }
+    
+    /// Expire a group that didn't fill up before its fill deadline
+    ///
+    /// Anyone can call this once the deadline has passed. The group is
+    /// cancelled and any recorded contributions are refunded.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to expire
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has already been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `GroupFull` - If the group filled up and has started
+    /// * `DeadlineNotReached` - If there is no deadline or it hasn't passed
+    /// * `ContractPaused` - If the contract is paused
+    /// * `Reentrancy` - If re-entered while funds are moving
+    pub fn expire_group(env: Env, group_id: u64) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // A group that started runs its course
+        if group.is_started {
+            return Err(AjoError::GroupFull);
+        }
+        
+        // Only after the fill deadline has passed
+        let fill_deadline = group.fill_deadline.ok_or(AjoError::DeadlineNotReached)?;
+        if utils::get_current_timestamp(&env) <= fill_deadline {
+            return Err(AjoError::DeadlineNotReached);
+        }
+        
+        // Return any deposits
+        utils::refund_open_contributions(&env, &group);
+        utils::refund_collateral(&env, &group);
+        utils::distribute_penalty_pool(&env, &mut group);
+        utils::distribute_dust(&env, &mut group);
+        
+        // The group no longer counts towards its members' groups
+        for member in group.members.iter() {
+            storage::remove_member_group(&env, &member, group_id);
+        }
+        
+        // Mark group as cancelled
+        group.is_cancelled = true;
+        group.is_complete = true;
+        storage::record_group_closed(&env);
+        
+        // Update storage
+        storage::store_group(&env, group_id, &group);
+        
+        // Emit event
+        events::emit_group_expired(&env, group_id, group.members.len(), fill_deadline);
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        
+        Ok(())
+    }
+    
+    /// Delete a finished group's records to stop paying rent on them
+    ///
+    /// Anyone can call this once the group has completed or been cancelled
+    /// and nobody is owed anything from it. The group entry goes, along with
+    /// every record kept for it, including those of members who left or
+    /// were removed, and the group is dropped from each member's group
+    /// list. Afterwards the group reads as not found. Invites that were
+    /// never taken up aren't tracked and stay behind.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to sweep
+    ///
+    /// # Returns
+    /// The number of storage entries removed
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `PayoutOwed` - If the group is still running, or a current or
+    ///   former member still has credit or collateral held in it (this
+    ///   stands in for a dedicated `GroupActive` error, as the error enum
+    ///   is at its size limit)
+    /// * `ContractPaused` - If the contract is paused
+    pub fn sweep_group(env: Env, group_id: u64) -> Result<u32, AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Get group
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only finished groups; cancelled ones are marked complete too
+        if !group.is_complete {
+            return Err(AjoError::PayoutOwed);
+        }
+        
+        // Everyone who was ever in the group
+        let mut addresses = group.members.clone();
+        addresses.append(&storage::get_departed_members(&env, group_id));
+        
+        // Funds still held for anyone would be stranded without the group
+        for member in addresses.iter() {
+            if storage::get_credit(&env, group_id, &member) > 0
+                || storage::get_collateral(&env, group_id, &member) > 0
+            {
+                return Err(AjoError::PayoutOwed);
+            }
+        }
+        
+        // Cycles never outnumber the group's member limit
+        let cycles = group.max_members.max(group.total_cycles);
+        let removed = storage::remove_group_records(&env, group_id, &addresses, cycles) + 1;
+        storage::remove_group(&env, group_id);
+        
+        // Emit event
+        events::emit_group_swept(&env, group_id, removed);
+        
+        Ok(removed)
+    }
+    
+    /// Return a group's held funds to its members while the contract is halted
+    ///
+    /// An escape hatch for when the contract has been paused over a critical
+    /// bug: every member gets back the contributions, collateral and late
+    /// fees the group holds for them, and the group is cancelled. Cycles
+    /// already paid out are left as they are.
+    ///
+    /// # Arguments
+    /// * `admin` - Address of the contract admin
+    /// * `group_id` - The group to refund
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the caller is not the admin, there is none, or
+    ///   the contract isn't paused
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has already been cancelled
     /// * `GroupComplete` - If the group has completed all cycles
-    pub fn contribute(env: Env, member: Address, group_id: u64) -> Result<(), AjoError> {
+    /// * `Reentrancy` - If re-entered while funds are moving
+    pub fn emergency_refund(env: Env, admin: Address, group_id: u64) -> Result<(), AjoError> {
+        let stored = storage::get_admin(&env).ok_or(AjoError::Unauthorized)?;
+        if admin != stored {
+            return Err(AjoError::Unauthorized);
+        }
+        admin.require_auth();
+        
+        // Only available while the contract is halted
+        if !storage::is_paused(&env) {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        // Check if group is complete
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        // Return any deposits
+        utils::refund_open_contributions(&env, &group);
+        utils::refund_collateral(&env, &group);
+        utils::distribute_penalty_pool(&env, &mut group);
+        utils::distribute_dust(&env, &mut group);
+        
+        // The group no longer counts towards its members' groups
+        for member in group.members.iter() {
+            storage::remove_member_group(&env, &member, group_id);
+        }
+        
+        // Mark group as cancelled
+        group.is_cancelled = true;
+        group.is_complete = true;
+        storage::record_group_closed(&env);
+        
+        // Update storage
+        storage::store_group(&env, group_id, &group);
+        
+        // Emit event
+        events::emit_group_cancelled(&env, group_id, &admin, group.current_cycle);
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        Ok(())
+    }
+    
+    /// Get how much collateral a group currently holds for a member
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    /// * `member` - The member to look up
+    ///
+    /// # Returns
+    /// The collateral still held, after any slashing or refunds
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_collateral(env: Env, group_id: u64, member: Address) -> Result<i128, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_collateral(&env, group_id, &member))
+    }
+    
+    /// Get the open dispute on a group's current cycle
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// The dispute, or `None` if the cycle isn't disputed
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_dispute(env: Env, group_id: u64) -> Result<Option<Dispute>, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_dispute(&env, group_id, group.current_cycle))
+    }
+    
+    /// Get the receipt for a member's contribution to a cycle
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    /// * `cycle` - The cycle number
+    /// * `member` - The member to look up
+    ///
+    /// # Returns
+    /// The amount paid, late fee included, and when, or `None` if the member
+    /// hasn't contributed to that cycle
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_contribution_record(
+        env: Env,
+        group_id: u64,
+        cycle: u32,
+        member: Address,
+    ) -> Result<Option<ContributionRecord>, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_contribution_record(&env, group_id, cycle, &member))
+    }
+    
+    /// Get how much a member contributes each cycle
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    /// * `member` - The member to look up
+    ///
+    /// # Returns
+    /// The member's agreed amount, or the group's flat amount if none was set
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `NotMember` - If the address is not a member
+    pub fn get_member_contribution(
+        env: Env,
+        group_id: u64,
+        member: Address,
+    ) -> Result<i128, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+        Ok(utils::member_contribution(&group, &member))
+    }
+    
+    /// Get the total value of contributions the group currently holds
+    ///
+    /// Sums every contribution recorded from the first cycle to the current
+    /// one, less what was sent out by payouts. A payout takes its whole cycle's
+    /// pool, so this is the current cycle's contributions plus any pool a
+    /// member waived that is still waiting to roll over.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// The amount of contributions held and not yet paid out
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `PayoutOverflow` - If the total does not fit in an i128
+    pub fn get_total_contributed(env: Env, group_id: u64) -> Result<i128, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        let mut total = storage::get_rollover(&env, group_id);
+        
+        // A completed group has paid out its final cycle too
+        if group.is_complete {
+            return Ok(total);
+        }
+        
+        for member in group.members.iter() {
+            if storage::has_contributed(&env, group_id, group.current_cycle, &member) {
+                total = total
+                    .checked_add(utils::contributed_amount(&env, &group, group.current_cycle, &member))
+                    .ok_or(AjoError::PayoutOverflow)?;
+            }
+        }
+        
+        Ok(total)
+    }
+    
+    /// Get a member's net position in a group
+    ///
+    /// Counts everything the member has paid into payouts, including
+    /// collateral slashed to cover them and contributions waiting for cycles
+    /// not yet paid out, against everything they've received from payouts
+    /// and bid shares. Across all members these net out to zero whenever no
+    /// contributions are waiting.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    /// * `member` - The member to look up
+    ///
+    /// # Returns
+    /// Amount received less amount paid in; positive once the member is ahead
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `NotMember` - If the address is not a member
+    pub fn get_member_balance(env: Env, group_id: u64, member: Address) -> Result<i128, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        if !utils::is_member(&group.members, &member) {
+            return Err(AjoError::NotMember);
+        }
+        
+        let (paid_in, received) = storage::get_member_flows(&env, group_id)
+            .get(member.clone())
+            .unwrap_or((0, 0));
+        let mut balance = received.saturating_sub(paid_in);
+        
+        // Contributions to cycles that haven't been paid out yet
+        if !group.is_complete {
+            let last_cycle = group.current_cycle.max(group.total_cycles);
+            for cycle in group.current_cycle..=last_cycle {
+                if storage::has_contributed(&env, group_id, cycle, &member) {
+                    let amount = utils::contributed_amount(&env, &group, cycle, &member);
+                    balance = balance.saturating_sub(amount);
+                }
+            }
+        }
+        
+        Ok(balance)
+    }
+    
+    /// Get how much a member has overpaid into a group
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    /// * `member` - The member to look up
+    ///
+    /// # Returns
+    /// The credit waiting to be withdrawn
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_credit(env: Env, group_id: u64, member: Address) -> Result<i128, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::get_credit(&env, group_id, &member))
+    }
+    
+    /// Withdraw whatever a member paid into a group beyond what was owed
+    ///
+    /// The credit is returned whatever state the group is in, including to
+    /// members who have since left or been removed.
+    ///
+    /// # Arguments
+    /// * `member` - Address of the member withdrawing
+    /// * `group_id` - The group the credit is held in
+    ///
+    /// # Returns
+    /// The amount returned
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `NoCredit` - If the member has nothing to withdraw
+    /// * `ContractPaused` - If the contract is paused
+    /// * `Reentrancy` - If re-entered while funds are moving
+    pub fn withdraw_credit(env: Env, member: Address, group_id: u64) -> Result<i128, AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Guard against re-entry while funds move
+        utils::acquire_lock(&env)?;
+        
         // Require authentication
         member.require_auth();
         
         // Get group
         let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
         
+        let credit = storage::get_credit(&env, group_id, &member);
+        if credit <= 0 {
+            return Err(AjoError::NoCredit);
+        }
+        
+        // Clear the credit before sending it back
+        storage::store_credit(&env, group_id, &member, 0);
+        utils::transfer_out(&env, &group, &member, credit);
+        
+        // Emit event
+        events::emit_credit_withdrawn(&env, group_id, &member, credit);
+        
+        // Release the reentrancy lock
+        utils::release_lock(&env);
+        Ok(credit)
+    }
+    
+    /// Get a member's reputation as their number of missed contributions
+    ///
+    /// # Arguments
+    /// * `member` - The address to look up
+    ///
+    /// # Returns
+    /// How many cycles the member failed to pay, across all groups
+    pub fn get_reputation(env: Env, member: Address) -> u32 {
+        storage::get_default_count(&env, &member)
+    }
+    
+    /// Hand the creator role of a group to another member
+    ///
+    /// # Arguments
+    /// * `current_creator` - Address of the current group creator
+    /// * `group_id` - The group to update
+    /// * `new_creator` - Existing member who becomes the creator
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `Unauthorized` - If the caller is not the group creator
+    /// * `NotMember` - If the new creator is not a member
+    /// * `GroupCancelled` - If the group has been cancelled
+    /// * `GroupComplete` - If the group has completed all cycles
+    /// * `ContractPaused` - If the contract is paused
+    pub fn transfer_creator(
+        env: Env,
+        current_creator: Address,
+        group_id: u64,
+        new_creator: Address,
+    ) -> Result<(), AjoError> {
+        // Reject while the contract is paused
+        utils::ensure_not_paused(&env)?;
+        
+        // Require authentication
+        current_creator.require_auth();
+        
+        // Get group
+        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        // Only the creator can hand over the role
+        if group.creator != current_creator {
+            return Err(AjoError::Unauthorized);
+        }
+        
+        // Check if group was cancelled
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
         // Check if group is complete
         if group.is_complete {
             return Err(AjoError::GroupComplete);
         }
         
-        // Check if member
+        // The new creator must already be in the group
+        if !utils::is_member(&group.members, &new_creator) {
+            return Err(AjoError::NotMember);
+        }
+        
+        // Update creator
+        group.creator = new_creator.clone();
+        
+        // Update storage
+        storage::store_group(&env, group_id, &group);
+        
+        // Emit event
+        events::emit_creator_transferred(&env, group_id, &current_creator, &new_creator);
+        
+        Ok(())
+    }
+    
+    /// Get how long is left to contribute to the current cycle
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// Seconds until the cycle deadline, or 0 once it has passed
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_time_remaining(env: Env, group_id: u64) -> Result<u64, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(utils::time_until_deadline(&env, &group))
+    }
+    
+    /// Get the member due to receive the current cycle's payout
+    ///
+    /// Follows the payout order when one has been shuffled, otherwise join
+    /// order. In auction mode this is whoever would win if paid out now.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// Address of the next recipient
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupComplete` - If every member has already been paid
+    pub fn get_next_recipient(env: Env, group_id: u64) -> Result<Address, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
+        if group.is_complete {
+            return Err(AjoError::GroupComplete);
+        }
+        
+        utils::select_payout_recipient(&env, &group)
+            .map(|(recipient, _)| recipient)
+            .ok_or(AjoError::GroupComplete)
+    }
+    
+    /// Get who each cycle pays, from cycle 1 on
+    ///
+    /// Paid cycles show the member who actually received, leaving off those
+    /// paid before the cycle was recorded and members who have since left.
+    /// Upcoming cycles follow the payout order, or join order if none was
+    /// set, skipping anyone already paid. Auction winners aren't known in
+    /// advance, and a cancelled group pays no more cycles, so neither lists
+    /// upcoming ones. Cycles whose member hasn't joined yet are left off.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// Vector of (cycle, recipient) tuples in cycle order
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn get_payout_schedule(env: Env, group_id: u64) -> Result<Vec<(u32, Address)>, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        let order = if group.payout_order.is_empty() {
+            &group.members
+        } else {
+            &group.payout_order
+        };
+        
+        // Paid members, keyed and so sorted by the cycle that paid them
+        let mut paid: Map<u32, Address> = Map::new(&env);
+        for member in group.members.iter() {
+            if let Some(cycle) = storage::get_payout_cycle(&env, group_id, &member) {
+                paid.set(cycle, member);
+            }
+        }
+        let mut schedule = Vec::new(&env);
+        for (cycle, member) in paid.iter() {
+            schedule.push_back((cycle, member));
+        }
+        
+        if group.is_complete || group.is_cancelled || group.payout_mode == PayoutMode::Auction {
+            return Ok(schedule);
+        }
+        
+        // The rest of the rotation, starting from the current slot
+        let start = usize::try_from(group.payout_index).unwrap_or(usize::MAX);
+        let mut upcoming: Vec<Address> = Vec::new(&env);
+        let mut cycle = group.current_cycle;
+        for member in order
+            .iter()
+            .skip(start)
+            .chain(order.iter().take(start))
+            .chain(group.members.iter())
+        {
+            if cycle > group.total_cycles {
+                break;
+            }
+            if upcoming.contains(&member) || storage::has_received_payout(&env, group_id, &member) {
+                continue;
+            }
+            upcoming.push_back(member.clone());
+            schedule.push_back((cycle, member));
+            cycle += 1;
+        }
+        Ok(schedule)
+    }
+    
+    /// Get how many cycles a member waits before their payout
+    ///
+    /// Counts from the member's slot in the payout order, or join order if
+    /// none was shuffled. In auction mode the order isn't fixed, so this is
+    /// only how long they'd wait if nobody outbid them.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to check
+    /// * `member` - The member to look up
+    ///
+    /// # Returns
+    /// 0 if the current cycle pays the member, otherwise the number of
+    /// payouts still ahead of theirs
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `NotMember` - If the address is not a member
+    /// * `AlreadyReceivedPayout` - If the member was already paid out
+    pub fn cycles_until_my_turn(env: Env, group_id: u64, member: Address) -> Result<u32, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        
         if !utils::is_member(&group.members, &member) {
             return Err(AjoError::NotMember);
         }
         
-        // Check if already contributed
-        if storage::has_contributed(&env, group_id, group.current_cycle, &member) {
-            return Err(AjoError::AlreadyContributed);
+        if storage::has_received_payout(&env, group_id, &member) {
+            return Err(AjoError::AlreadyReceivedPayout);
         }
         
-        // Transfer contribution to contract
-        // Note: In production, this would use token.transfer() or native transfer
-        // For now, we mark as contributed (assuming payment succeeded)
-        
-        // Record contribution
-        storage::store_contribution(&env, group_id, group.current_cycle, &member, true);
-        
-        // Emit event
-        events::emit_contribution_made(
-            &env,
-            group_id,
-            &member,
-            group.current_cycle,
-            group.contribution_amount,
-        );
-        
-        Ok(())
+        let order = if group.payout_order.is_empty() {
+            &group.members
+        } else {
+            &group.payout_order
+        };
+        let position = order.first_index_of(&member).ok_or(AjoError::NotMember)?;
+        Ok(position.saturating_sub(group.payout_index))
     }
     
-    /// Get contribution status for all members in the current cycle
+    /// Check if a group has completed all cycles
+    ///
+    /// A cancelled or expired group is also reported complete, since it will
+    /// run no further cycles; use `is_cancelled` or `get_state` to tell the
+    /// two apart.
     ///
     /// # Arguments
     /// * `group_id` - The group to check
-    /// * `cycle_number` - The cycle to check (use current_cycle from group)
     ///
     /// # Returns
-    /// Vector of (Address, bool) tuples indicating contribution status
+    /// True if the group is complete, false otherwise
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    pub fn get_contribution_status(
-        env: Env,
-        group_id: u64,
-        cycle_number: u32,
-    ) -> Result<Vec<(Address, bool)>, AjoError> {
+    pub fn is_complete(env: Env, group_id: u64) -> Result<bool, AjoError> {
         let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
-        Ok(storage::get_cycle_contributions(
-            &env,
-            group_id,
-            cycle_number,
-            &group.members,
-        ))
+        Ok(group.is_complete)
     }
     
-    /// Execute payout for the current cycle
+    /// Check if a group was cancelled or expired before finishing
     ///
-    /// This function:
-    /// 1. Verifies all members have contributed
-    /// 2. Calculates and sends payout to the next member
-    /// 3. Advances the payout index and cycle
-    /// 4. Marks group as complete if all members have received payout
+    /// # Arguments
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// True if the group was cancelled, false otherwise
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    pub fn is_cancelled(env: Env, group_id: u64) -> Result<bool, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(group.is_cancelled)
+    }
+    
+    /// Get where a group is in its life
     ///
     /// # Arguments
-    /// * `group_id` - The group to execute payout for
+    /// * `group_id` - The group to check
+    ///
+    /// # Returns
+    /// `Recruiting`, `Active`, `Completed` or `Cancelled`
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    /// * `IncompleteContributions` - If not all members have contributed
-    /// * `GroupComplete` - If the group has already completed
-    /// * `NoMembers` - If the group has no members (should never happen)
-    pub fn execute_payout(env: Env, group_id: u64) -> Result<(), AjoError> {
-        // Get group
-        let mut group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
-        
-        // Check if group is complete
-        if group.is_complete {
-            return Err(AjoError::GroupComplete);
-        }
-        
-        // Check if all members have contributed
-        if !utils::all_members_contributed(&env, &group) {
-            return Err(AjoError::IncompleteContributions);
-        }
-        
-        // Get payout recipient
-        let payout_recipient = group
-            .members
-            .get(group.payout_index)
-            .ok_or(AjoError::NoMembers)?;
-        
-        // Calculate payout amount
-        let payout_amount = utils::calculate_payout_amount(&group);
-        
-        // Transfer payout to recipient
-        // Note: In production, this would use token.transfer() or native transfer
-        // For now, we just record it
-        
-        // Mark payout as received
-        storage::mark_payout_received(&env, group_id, &payout_recipient);
-        
-        // Emit payout event
-        events::emit_payout_executed(
-            &env,
-            group_id,
-            &payout_recipient,
-            group.current_cycle,
-            payout_amount,
-        );
-        
-        // Advance payout index
-        group.payout_index += 1;
-        
-        // Check if all members have received payout
-        if group.payout_index >= group.members.len() {
-            // All members have received payout - mark complete
-            group.is_complete = true;
-            events::emit_group_completed(&env, group_id);
+    pub fn get_state(env: Env, group_id: u64) -> Result<GroupState, AjoError> {
+        let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        let state = if group.is_cancelled {
+            GroupState::Cancelled
+        } else if group.is_complete {
+            GroupState::Completed
+        } else if group.is_started {
+            GroupState::Active
         } else {
-            // Advance to next cycle
-            group.current_cycle += 1;
-            group.cycle_start_time = utils::get_current_timestamp(&env);
-        }
-        
-        // Update storage
-        storage::store_group(&env, group_id, &group);
-        
-        Ok(())
+            GroupState::Recruiting
+        };
+        Ok(state)
     }
     
-    /// Check if a group has completed all cycles
+    /// Check whether a cycle's payout has already gone out
+    ///
+    /// Each payout records its cycle as it is made, so a group that was
+    /// finished without paying a cycle, such as one its members reclaimed
+    /// from, reports that cycle as unpaid.
     ///
     /// # Arguments
     /// * `group_id` - The group to check
+    /// * `cycle` - The cycle number (starting at 1)
     ///
     /// # Returns
-    /// True if the group is complete, false otherwise
+    /// `true` once the cycle has been paid out
     ///
     /// # Errors
     /// * `GroupNotFound` - If the group does not exist
-    pub fn is_complete(env: Env, group_id: u64) -> Result<bool, AjoError> {
+    pub fn is_cycle_paid(env: Env, group_id: u64, cycle: u32) -> Result<bool, AjoError> {
+        storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
+        Ok(storage::is_cycle_paid(&env, group_id, cycle))
+    }
+    
+    /// Estimate when a group will finish its final cycle
+    ///
+    /// Assumes each remaining cycle runs its full duration from the start of
+    /// the current one.
+    ///
+    /// # Arguments
+    /// * `group_id` - The group to estimate
+    ///
+    /// # Returns
+    /// The projected completion timestamp, or the actual one once the group
+    /// has completed
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group does not exist
+    /// * `GroupCancelled` - If the group has been cancelled
+    pub fn get_completion_estimate(env: Env, group_id: u64) -> Result<u64, AjoError> {
         let group = storage::get_group(&env, group_id).ok_or(AjoError::GroupNotFound)?;
-        Ok(group.is_complete)
+        
+        // Cancelled groups never complete
+        if group.is_cancelled {
+            return Err(AjoError::GroupCancelled);
+        }
+        
+        if let Some(completed_at) = group.completed_at {
+            return Ok(completed_at);
+        }
+        
+        let remaining_cycles = (group.total_cycles + 1).saturating_sub(group.current_cycle);
+        Ok(group
+            .cycle_start_time
+            .saturating_add(group.cycle_duration.saturating_mul(u64::from(remaining_cycles))))
     }
     
     /// Get comprehensive group status
@@ -372,7 +3903,7 @@ impl AjoContract {
         let current_time = utils::get_current_timestamp(&env);
         
         // Calculate cycle timing
-        let cycle_end_time = group.cycle_start_time + group.cycle_duration;
+        let cycle_end_time = group.cycle_start_time.saturating_add(group.cycle_duration);
         let is_cycle_active = current_time < cycle_end_time;
         
         // Get contribution status for all members in current cycle
@@ -400,10 +3931,8 @@ impl AjoContract {
             // Use placeholder (creator) when complete
             (false, group.creator.clone())
         } else {
-            // Get the member at payout_index
-            let recipient = group
-                .members
-                .get(group.payout_index)
+            // Get the member scheduled for the current slot
+            let recipient = utils::scheduled_recipient(&group)
                 .unwrap_or_else(|| group.creator.clone());
             (true, recipient)
         };
@@ -415,7 +3944,7 @@ impl AjoContract {
             has_next_recipient,
             next_recipient,
             contributions_received,
-            total_members: group.members.len() as u32,
+            total_members: group.members.len(),
             pending_contributors,
             is_complete: group.is_complete,
             is_cycle_active,
@@ -425,3 +3954,676 @@ impl AjoContract {
         })
     }
 }
+
+/// Check the parameters and settings a group would be created with
+///
+/// Shared by `create_group_with_config` and `save_template`, so a template
+/// that saves is one a group can be created from. Returns the token's
+/// decimals.
+fn validate_group_config(
+    env: &Env,
+    token: &Address,
+    contribution_amount: i128,
+    cycle_duration: u64,
+    max_members: u32,
+    config: &GroupConfig,
+) -> Result<u32, AjoError> {
+    utils::validate_group_params(contribution_amount, cycle_duration, max_members)?;
+    let decimals = utils::validate_token(env, token)?;
+    if config.collateral_amount < 0 {
+        return Err(AjoError::ContributionAmountNegative);
+    }
+    let total_cycles = config.total_cycles.unwrap_or(max_members);
+    if total_cycles < 1 || total_cycles > max_members {
+        return Err(AjoError::InvalidCycleCount);
+    }
+    let min_members = config.min_members.unwrap_or(max_members);
+    if min_members < 2 || min_members > max_members {
+        return Err(AjoError::InvalidMinMembers);
+    }
+    if config.penalty_bps > utils::BPS_DENOMINATOR {
+        return Err(AjoError::InvalidPenalty);
+    }
+    if config.commission_bps > utils::MAX_COMMISSION_BPS {
+        return Err(AjoError::InvalidCommission);
+    }
+    
+    // An oracle-priced group needs a value to hold. Late fees are a share
+    // of a fixed amount, so they can't be combined with one
+    if config.oracle.is_some() {
+        if config.target_value <= 0 {
+            return Err(AjoError::ContributionAmountZero);
+        }
+        if config.penalty_bps > 0 {
+            return Err(AjoError::InvalidPenalty);
+        }
+    }
+    
+    Ok(decimals)
+}
+
+/// Check that a member can contribute to a group at all
+///
+/// Shared by every way of contributing, so they accept and refuse alike.
+fn ensure_can_contribute(group: &Group, member: &Address) -> Result<(), AjoError> {
+    // Check if group was cancelled
+    if group.is_cancelled {
+        return Err(AjoError::GroupCancelled);
+    }
+    
+    // Check if group is complete
+    if group.is_complete {
+        return Err(AjoError::GroupComplete);
+    }
+    
+    // Nothing moves while the creator has the group paused
+    if group.is_paused {
+        return Err(AjoError::GroupPaused);
+    }
+    
+    // Check if member
+    if !utils::is_member(&group.members, member) {
+        return Err(AjoError::NotMember);
+    }
+    
+    // Contributions open once the group has started
+    if !group.is_started {
+        return Err(AjoError::GroupNotFull);
+    }
+    
+    Ok(())
+}
+
+/// Work out the late fee on a contribution to the current cycle made at `now`
+///
+/// Contributions are only accepted until the cycle deadline, unless the
+/// group gives a grace period, which carries any late fee.
+fn late_fee(group: &Group, member: &Address, now: u64) -> Result<i128, AjoError> {
+    if utils::is_within_cycle_window(group, now) {
+        return Ok(0);
+    }
+    if !utils::is_within_grace_period(group, now) {
+        return Err(AjoError::ContributionWindowClosed);
+    }
+    utils::calculate_late_penalty(group, member)
+}
+
+/// Check and record a member's contribution to the current cycle
+///
+/// Runs every check `contribute` makes and records the contribution, but
+/// leaves collecting the returned amount to the caller so batches can move
+/// each token once.
+fn record_contribution(
+    env: &Env,
+    member: &Address,
+    group_id: u64,
+) -> Result<(Group, i128), AjoError> {
+    // Get group
+    let mut group = storage::get_group(env, group_id).ok_or(AjoError::GroupNotFound)?;
+    ensure_can_contribute(&group, member)?;
+    
+    // Check if already contributed; a resubmitted transaction must fail
+    // here, before any funds move
+    if storage::has_contributed(env, group_id, group.current_cycle, member) {
+        return Err(AjoError::AlreadyContributed);
+    }
+    
+    let now = utils::get_current_timestamp(env);
+    let penalty = late_fee(&group, member, now)?;
+    
+    // The contribution plus any late fee is owed to the contract
+    let amount = utils::contribution_due(env, &group, member)?;
+    let cycle = group.current_cycle;
+    let total = store_cycle_contribution(env, &mut group, member, cycle, amount, penalty, now)?;
+    
+    Ok((group, total))
+}
+
+/// Record a member's contribution of `amount`, plus a late `fee`, to `cycle`
+///
+/// Shared by every way of contributing, so each recorded cycle pools its
+/// fee and emits the same events. Returns what the record holds.
+fn store_cycle_contribution(
+    env: &Env,
+    group: &mut Group,
+    member: &Address,
+    cycle: u32,
+    amount: i128,
+    fee: i128,
+    now: u64,
+) -> Result<i128, AjoError> {
+    let total = amount.checked_add(fee).ok_or(AjoError::PayoutOverflow)?;
+    
+    // Record contribution
+    let record = ContributionRecord {
+        amount: total,
+        timestamp: now,
+    };
+    storage::store_contribution(env, group.id, cycle, member, &record);
+    
+    // Late fees are pooled until the group ends
+    if fee > 0 {
+        group.penalty_pool = group
+            .penalty_pool
+            .checked_add(fee)
+            .ok_or(AjoError::PayoutOverflow)?;
+        storage::store_group(env, group.id, group);
+        events::emit_late_penalty(env, group.id, member, cycle, fee);
+    }
+    
+    // Emit event
+    events::emit_contribution_made(env, group.id, member, cycle, total);
+    
+    // Let keepers know the current cycle can be paid out
+    if cycle == group.current_cycle && utils::all_members_contributed(env, group) {
+        events::emit_cycle_ready(env, group.id, cycle);
+    }
+    
+    Ok(total)
+}
+
+/// Fill in the fields a version 1 group lacks
+///
+/// Legacy groups ran one cycle per member with no optional settings, and
+/// count as started once full or once a payout has gone out.
+fn upgrade_legacy_group(env: &Env, legacy: LegacyGroupV1, token: &Address) -> Group {
+    let is_started = legacy.members.len() == legacy.max_members
+        || legacy.payout_index > 0
+        || legacy.is_complete;
+    Group {
+        id: legacy.id,
+        creator: legacy.creator,
+        token: token.clone(),
+        contribution_amount: legacy.contribution_amount,
+        cycle_duration: legacy.cycle_duration,
+        max_members: legacy.max_members,
+        members: legacy.members,
+        current_cycle: legacy.current_cycle,
+        payout_index: legacy.payout_index,
+        created_at: legacy.created_at,
+        cycle_start_time: legacy.cycle_start_time,
+        is_started,
+        started_at: None,
+        is_complete: legacy.is_complete,
+        is_cancelled: false,
+        is_paused: false,
+        payout_mode: PayoutMode::RotationFixed,
+        payout_order: Vec::new(env),
+        fill_deadline: None,
+        collateral_amount: 0,
+        total_cycles: legacy.max_members,
+        penalty_bps: 0,
+        penalty_pool: 0,
+        is_private: false,
+        allow_forfeiture: false,
+        completed_at: None,
+        commission_bps: 0,
+        min_members: legacy.max_members,
+        contribution_policy: ContributionPolicy::Strict,
+        contribution_by_member: Map::new(env),
+        last_payout_time: None,
+        oracle: None,
+        target_value: 0,
+        payout_caller: None,
+        round_payouts: false,
+        dust_pool: 0,
+    }
+}
+
+/// Check that a group's current cycle can be paid out by `execute_payout`
+fn ensure_payout_ready(env: &Env, group: &Group, now: u64) -> Result<(), AjoError> {
+    // Check if group was cancelled
+    if group.is_cancelled {
+        return Err(AjoError::GroupCancelled);
+    }
+    
+    // Check if group is complete
+    if group.is_complete {
+        return Err(AjoError::GroupComplete);
+    }
+    
+    // Nothing moves while the creator has the group paused
+    if group.is_paused {
+        return Err(AjoError::GroupPaused);
+    }
+    
+    // No cycle runs until the group has started
+    if !group.is_started {
+        return Err(AjoError::GroupNotFull);
+    }
+    
+    // A disputed cycle waits for the creator to resolve it
+    if storage::get_dispute(env, group.id, group.current_cycle).is_some() {
+        return Err(AjoError::CycleDisputed);
+    }
+    
+    // A cycle nobody has paid into, and no collateral or guarantor
+    // covers, has nothing to pay out
+    if !utils::any_member_covered(env, group) {
+        return Err(AjoError::IncompleteContributions);
+    }
+    
+    // Check if all members have contributed; once the contribution window
+    // has closed the cycle is paid out with whatever was collected
+    let funded = utils::all_members_contributed(env, group);
+    if !funded && !utils::is_cycle_expired(group, now) {
+        // Past the deadline, late contributions are still coming in
+        if !utils::is_within_cycle_window(group, now) {
+            return Err(AjoError::CycleNotReady);
+        }
+        return Err(AjoError::IncompleteContributions);
+    }
+    
+    // An underfunded cycle waits a full cycle after the last payout
+    if !funded && utils::is_payout_too_soon(group, now) {
+        return Err(AjoError::CycleNotReady);
+    }
+    
+    Ok(())
+}
+
+/// Work out who `settle_cycle` would pay and how much, without paying
+///
+/// Counts what each member paid, or what their guarantor or collateral
+/// would cover, and divides the pool with the same `split_payout`.
+fn preview_payout(env: &Env, group: &Group) -> Result<(Address, i128), AjoError> {
+    let (payout_recipient, winning_bid) =
+        utils::select_payout_recipient(env, group).ok_or(AjoError::NoMembers)?;
+    
+    // What each member paid, or what their guarantor or collateral covers
+    let mut payout_amount: i128 = 0;
+    let mut contributors: i128 = 0;
+    for member in group.members.iter() {
+        let owed = utils::member_contribution(group, &member);
+        let covered = storage::get_guarantor(env, group.id, &member)
+            .is_some_and(|guarantor| utils::can_draw_from(env, group, &guarantor, owed));
+        let contributed = storage::has_contributed(env, group.id, group.current_cycle, &member);
+        if (contributed || covered) && member != payout_recipient {
+            contributors += 1;
+        }
+        let collected = if contributed {
+            utils::contributed_amount(env, group, group.current_cycle, &member)
+        } else if covered {
+            owed
+        } else {
+            storage::get_collateral(env, group.id, &member).min(owed).max(0)
+        };
+        payout_amount = payout_amount
+            .checked_add(collected)
+            .ok_or(AjoError::PayoutOverflow)?;
+    }
+    
+    // A waived turn rolls the pool on unless it is the last payout
+    let is_last_payout = group.payout_index + 1 >= utils::rotation_length(group);
+    let waived = storage::has_waived_payout(env, group.id, &payout_recipient) && !is_last_payout;
+    let split = split_payout(
+        env,
+        group,
+        payout_amount,
+        winning_bid,
+        contributors,
+        storage::get_rollover(env, group.id),
+        waived,
+    )?;
+    if waived {
+        return Ok((payout_recipient, 0));
+    }
+    
+    Ok((payout_recipient, split.amount - split.dust))
+}
+
+/// How a cycle's pool is divided once it has been collected
+struct PayoutSplit {
+    /// What each other contributor receives of the winning bid
+    bid_share: i128,
+    
+    /// The platform fee and who collects it, when a fee is configured
+    platform_fee: Option<(Address, i128)>,
+    
+    /// The creator's commission
+    commission: i128,
+    
+    /// What the recipient is owed, rollover included; on a waived turn,
+    /// what rolls on to the next recipient
+    amount: i128,
+    
+    /// The fraction of a token a rounded group holds back from `amount`
+    dust: i128,
+}
+
+/// Divide a collected pool between the recipient, the other contributors,
+/// the platform and the creator
+///
+/// The winning bid, capped at the pool, is shared among the other
+/// `contributors`; any indivisible remainder stays with the recipient. The
+/// platform fee comes off the top, then the creator's commission is taken
+/// from what is left, and any pool an earlier recipient waived is added.
+/// Moves nothing, so `settle_cycle` and `preview_payout` agree by
+/// construction.
+fn split_payout(
+    env: &Env,
+    group: &Group,
+    collected: i128,
+    winning_bid: i128,
+    contributors: i128,
+    rollover: i128,
+    waived: bool,
+) -> Result<PayoutSplit, AjoError> {
+    let mut amount = collected;
+    
+    // The other contributors' shares of the winning bid
+    let mut bid_share = 0;
+    let discount = winning_bid.min(amount);
+    if discount > 0 && contributors > 0 {
+        (bid_share, _) = utils::split_evenly(discount, contributors);
+        amount -= bid_share * contributors;
+    }
+    
+    // Platform fee, then commission on the rest
+    let platform_fee = utils::calculate_platform_fee(env, amount)?;
+    if let Some((_, fee)) = &platform_fee {
+        amount -= fee;
+    }
+    let commission = utils::calculate_commission(group, amount)?;
+    amount -= commission;
+    
+    amount = amount.checked_add(rollover).ok_or(AjoError::PayoutOverflow)?;
+    
+    // Rounded groups hold back the fraction of a token, unless the pool
+    // rolls on whole
+    let dust = if group.round_payouts && !waived {
+        amount % utils::token_unit(env, &group.token)?
+    } else {
+        0
+    };
+    
+    Ok(PayoutSplit {
+        bid_share,
+        platform_fee,
+        commission,
+        amount,
+        dust,
+    })
+}
+
+/// Pay out the current cycle of a group that is ready for it
+///
+/// Sends whatever was actually collected, plus any pool an earlier recipient
+/// waived, to the cycle's recipient, records a
+/// default against every member who didn't pay, and advances the group to
+/// the next cycle or marks it complete.
+///
+/// Returns the recipient and the amount they were paid, which is 0 when
+/// they waived their turn.
+fn settle_cycle(env: &Env, mut group: Group, now: u64) -> Result<(Address, i128), AjoError> {
+    // Get payout recipient and, in auction mode, the winning bid
+    let (payout_recipient, winning_bid) =
+        utils::select_payout_recipient(env, &group).ok_or(AjoError::NoMembers)?;
+    
+    // Calculate payout amount from what was actually collected. A member
+    // who didn't pay this cycle is covered by their guarantor if they have
+    // one who can; otherwise a default is recorded and their share comes
+    // out of their collateral where possible
+    let mut payout_amount: i128 = 0;
+    let mut flows = storage::get_member_flows(env, group.id);
+    let mut contributors = Vec::new(env);
+    for member in group.members.iter() {
+        let owed = utils::member_contribution(&group, &member);
+        let paid = storage::has_contributed(env, group.id, group.current_cycle, &member)
+            || cover_from_guarantor(env, &group, &member);
+        if paid {
+            if member != payout_recipient {
+                contributors.push_back(member.clone());
+            }
+            let owed = utils::contributed_amount(env, &group, group.current_cycle, &member);
+            payout_amount = payout_amount
+                .checked_add(owed)
+                .ok_or(AjoError::PayoutOverflow)?;
+            utils::add_member_flow(&mut flows, &member, owed, 0);
+        } else {
+            let defaults = storage::increment_default(env, &member);
+            storage::increment_group_default(env, group.id, &member);
+            events::emit_member_defaulted(env, group.id, &member, group.current_cycle, defaults);
+            
+            let held = storage::get_collateral(env, group.id, &member);
+            let slashed = held.min(owed);
+            if slashed > 0 {
+                storage::store_collateral(env, group.id, &member, held - slashed);
+                payout_amount = payout_amount
+                    .checked_add(slashed)
+                    .ok_or(AjoError::PayoutOverflow)?;
+                utils::add_member_flow(&mut flows, &member, slashed, 0);
+                events::emit_collateral_slashed(
+                    env,
+                    group.id,
+                    &member,
+                    group.current_cycle,
+                    slashed,
+                );
+            }
+        }
+    }
+    
+    // A member who waived their turn leaves the pool for the next
+    // recipient. The last payout has no one to roll into, so it is paid
+    let is_last_payout = group.payout_index + 1 >= utils::rotation_length(&group);
+    let waived = storage::has_waived_payout(env, group.id, &payout_recipient) && !is_last_payout;
+    let rollover = storage::get_rollover(env, group.id);
+    let split = split_payout(
+        env,
+        &group,
+        payout_amount,
+        winning_bid,
+        i128::from(contributors.len()),
+        rollover,
+        waived,
+    )?;
+    
+    // Pay the other members who paid in their share of the winning bid. If
+    // defaults left the pool short of the bid, the whole pool is shared
+    if split.bid_share > 0 {
+        for member in contributors.iter() {
+            utils::transfer_out(env, &group, &member, split.bid_share);
+            utils::add_member_flow(&mut flows, &member, 0, split.bid_share);
+            events::emit_bid_share(env, group.id, &member, group.current_cycle, split.bid_share);
+        }
+    }
+    
+    // Pay the platform fee and the creator's commission
+    if let Some((collector, fee)) = split.platform_fee {
+        if fee > 0 {
+            utils::transfer_out(env, &group, &collector, fee);
+            events::emit_platform_fee_paid(env, group.id, &collector, group.current_cycle, fee);
+        }
+    }
+    let commission = split.commission;
+    if commission > 0 {
+        utils::transfer_out(env, &group, &group.creator, commission);
+        events::emit_commission_paid(
+            env,
+            group.id,
+            &group.creator,
+            group.current_cycle,
+            commission,
+        );
+    }
+    
+    // A waived turn leaves the pool for the next recipient
+    if waived {
+        storage::store_rollover(env, group.id, split.amount);
+        storage::store_member_flows(env, group.id, &flows);
+        storage::mark_payout_received(env, group.id, &payout_recipient, group.current_cycle);
+        events::emit_payout_waived(
+            env,
+            group.id,
+            &payout_recipient,
+            group.current_cycle,
+            split.amount,
+        );
+        advance_after_payout(env, group, now)?;
+        return Ok((payout_recipient, 0));
+    }
+    if rollover > 0 {
+        storage::store_rollover(env, group.id, 0);
+    }
+    
+    // Groups paying whole tokens hold the fraction back until they end
+    let payout_amount = split.amount - split.dust;
+    if split.dust > 0 {
+        group.dust_pool = group
+            .dust_pool
+            .checked_add(split.dust)
+            .ok_or(AjoError::PayoutOverflow)?;
+    }
+    
+    // Transfer payout to recipient, or to the address they delegated
+    let destination = storage::get_payout_destination(env, group.id, &payout_recipient);
+    utils::transfer_out(
+        env,
+        &group,
+        destination.as_ref().unwrap_or(&payout_recipient),
+        payout_amount,
+    );
+    if let Some(destination) = destination {
+        events::emit_payout_delegated(
+            env,
+            group.id,
+            &payout_recipient,
+            &destination,
+            group.current_cycle,
+            payout_amount,
+        );
+    }
+    
+    // Mark payout as received
+    storage::mark_payout_received(env, group.id, &payout_recipient, group.current_cycle);
+    utils::add_member_flow(&mut flows, &payout_recipient, 0, payout_amount);
+    storage::store_member_flows(env, group.id, &flows);
+    
+    // Emit payout event
+    events::emit_payout_executed(
+        env,
+        group.id,
+        &payout_recipient,
+        group.current_cycle,
+        payout_amount,
+    );
+    
+    advance_after_payout(env, group, now)?;
+    Ok((payout_recipient, payout_amount))
+}
+
+/// Move a group on to its next cycle, or complete it, once a payout is settled
+fn advance_after_payout(env: &Env, mut group: Group, now: u64) -> Result<(), AjoError> {
+    // The cycle's payout, or its waiver, has gone out
+    storage::mark_cycle_paid(env, group.id, group.current_cycle);
+    
+    // Advance payout index
+    group.payout_index += 1;
+    group.last_payout_time = Some(now);
+    
+    // Check if the group has run all its cycles
+    if group.payout_index >= utils::rotation_length(&group) {
+        finish_group(env, &mut group, now);
+    } else {
+        // Advance to next cycle
+        group.current_cycle += 1;
+        group.cycle_start_time = now;
+        utils::remind_members(env, &group);
+    }
+    
+    // Update storage
+    storage::store_group(env, group.id, &group);
+    
+    Ok(())
+}
+
+/// Complete a group whose remaining members have all been paid out
+///
+/// Marks it complete, reports where each member stands, returns collateral,
+/// pays the late fees out as a bonus to members who never defaulted and
+/// shares out any dust. The caller stores the group.
+fn finish_group(env: &Env, group: &mut Group, now: u64) {
+    group.is_complete = true;
+    group.completed_at = Some(now);
+    storage::record_group_completed(env);
+    utils::settle_member_balances(env, group);
+    utils::refund_collateral(env, group);
+    utils::pay_referral_bonuses(env, group);
+    utils::distribute_completion_bonus(env, group);
+    utils::distribute_dust(env, group);
+    events::emit_group_completed(env, group.id);
+}
+
+/// Collect a member's missed contribution from their guarantor
+///
+/// Records the contribution and returns `true` if the guarantor paid.
+fn cover_from_guarantor(env: &Env, group: &Group, member: &Address) -> bool {
+    let guarantor = match storage::get_guarantor(env, group.id, member) {
+        Some(guarantor) => guarantor,
+        None => return false,
+    };
+    let amount = utils::member_contribution(group, member);
+    if !utils::transfer_from_guarantor(env, group, &guarantor, amount) {
+        return false;
+    }
+    let record = ContributionRecord {
+        amount,
+        timestamp: utils::get_current_timestamp(env),
+    };
+    storage::store_contribution(env, group.id, group.current_cycle, member, &record);
+    events::emit_guarantor_covered(
+        env,
+        group.id,
+        member,
+        &guarantor,
+        group.current_cycle,
+        amount,
+    );
+    true
+}
+
+/// Set or clear a group's creator-controlled pause flag
+fn set_group_paused(
+    env: &Env,
+    creator: &Address,
+    group_id: u64,
+    paused: bool,
+) -> Result<(), AjoError> {
+    // Reject while the contract is paused
+    utils::ensure_not_paused(env)?;
+    
+    // Require authentication
+    creator.require_auth();
+    
+    // Get group
+    let mut group = storage::get_group(env, group_id).ok_or(AjoError::GroupNotFound)?;
+    
+    // Only the creator can pause or resume
+    if group.creator != *creator {
+        return Err(AjoError::Unauthorized);
+    }
+    
+    // Check if group was cancelled
+    if group.is_cancelled {
+        return Err(AjoError::GroupCancelled);
+    }
+    
+    // Check if group is complete
+    if group.is_complete {
+        return Err(AjoError::GroupComplete);
+    }
+    
+    // Update storage
+    group.is_paused = paused;
+    storage::store_group(env, group_id, &group);
+    
+    // Emit event
+    if paused {
+        events::emit_group_paused(env, group_id, creator);
+    } else {
+        events::emit_group_resumed(env, group_id, creator);
+    }
+    
+    Ok(())
+}
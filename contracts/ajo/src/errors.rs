@@ -14,13 +14,19 @@ pub enum AjoError {
     /// This account is already part of the group.
     AlreadyMember = 3,
     
-    /// Address isn't a member of the group.
+    /// Address isn't a member of the group. Also stands in for a referrer
+    /// `join_group_with_referral` can't accept because they aren't in the
+    /// group, the joiner themselves included (`InvalidReferrer`).
     NotMember = 4,
     
     /// You've already made your contribution for this cycle.
     AlreadyContributed = 5,
     
-    /// We can't move forward until everyone has contributed.
+    /// We can't move forward until everyone has contributed. Also stands in
+    /// for a payout on a cycle that isn't funded (`CycleNotFunded`): its
+    /// window is still open with contributions missing, or nobody paid in
+    /// and no collateral or guarantor covers them. `get_delinquent_members`
+    /// lists the shortfall.
     IncompleteContributions = 6,
     
     /// Member has already been paid out.
@@ -29,16 +35,22 @@ pub enum AjoError {
     /// All cycles for this group are finished.
     GroupComplete = 8,
     
-    /// Contribution amount can't be zero.
+    /// Contribution amount can't be zero, including an oracle-priced one
+    /// whose target value converts to no tokens at the current price.
     ContributionAmountZero = 9,
     
     /// Cycle duration must be greater than zero.
     CycleDurationZero = 10,
     
+    /// Cycle duration can't be longer than ten years.
+    CycleDurationTooLong = 51,
+    
     /// Groups need at least 2 members to work.
     MaxMembersBelowMinimum = 11,
     
-    /// Max members exceeds reasonable limit.
+    /// Max members exceed reasonable limit. Also stands in for the caps on
+    /// how many groups are asked for at once (`MAX_BATCH_IDS`) and how many
+    /// running groups one address can be in (`MAX_ACTIVE_GROUPS`).
     MaxMembersAboveLimit = 18,
     
     /// Member doesn't have enough balance.
@@ -47,7 +59,9 @@ pub enum AjoError {
     /// The token transfer didn't go through.
     TransferFailed = 13,
     
-    /// This group has no members initialized.
+    /// This group has no members initialized. Also stands in for a group
+    /// that can no longer rotate (`GroupUnderpopulated`): a kick that would
+    /// leave no unpaid member to receive the next payout.
     NoMembers = 14,
     
     /// Only the creator or authorized members can do this.
@@ -64,4 +78,101 @@ pub enum AjoError {
     
     /// The contract has already been initialized.
     AlreadyInitialized = 20,
+    
+    /// A bid must be positive, smaller than the cycle's pool, and placed in a
+    /// group running in auction mode.
+    InvalidBid = 22,
+    
+    /// The payout order has already been fixed for this group.
+    OrderAlreadySet = 23,
+    
+    /// The group still has open member slots.
+    GroupNotFull = 24,
+    
+    /// The current cycle's contribution deadline has passed.
+    ContributionWindowClosed = 25,
+    
+    /// The contract is paused by its admin. Also stands in for new group
+    /// creation being stopped with `set_creation_enabled` (`CreationDisabled`),
+    /// whichever way the group would have been created.
+    ContractPaused = 26,
+    
+    /// The payout for a cycle would overflow.
+    PayoutOverflow = 27,
+    
+    /// The group name or description is longer than allowed.
+    MetadataTooLong = 28,
+    
+    /// Can't prepay for cycles beyond the group's final cycle.
+    ExceedsRemainingCycles = 29,
+    
+    /// The group's fill deadline hasn't passed yet.
+    DeadlineNotReached = 30,
+    
+    /// The group already has all its members.
+    GroupFull = 31,
+    
+    /// A guarded call was re-entered before it finished.
+    Reentrancy = 32,
+    
+    /// The cycle count must be between 1 and the group's member limit.
+    InvalidCycleCount = 33,
+    
+    /// The group has been paused by its creator.
+    GroupPaused = 34,
+    
+    /// The late penalty can't exceed 10000 basis points.
+    InvalidPenalty = 35,
+    
+    /// The contract has no admin yet; call `initialize` first.
+    NotInitialized = 36,
+    
+    /// This private group requires an invite from its creator.
+    NotInvited = 37,
+    
+    /// The token must be a contract address, with a fresh oracle price when
+    /// the group is priced by one. Also stands in for a missing or stale
+    /// oracle price (`StalePrice`), and for a legacy group `migrate` was
+    /// given no token for.
+    InvalidToken = 38,
+    
+    /// The member hasn't received their payout yet, or the group still has
+    /// payouts to make.
+    PayoutOwed = 39,
+    
+    /// A payout position that has already been paid out can't be moved.
+    PositionAlreadyPaid = 40,
+    
+    /// The creator commission can't exceed 1000 basis points.
+    InvalidCommission = 41,
+    
+    /// Stored data is already on the current schema version.
+    AlreadyMigrated = 42,
+    
+    /// The minimum member count must be between 2 and max_members.
+    InvalidMinMembers = 43,
+    
+    /// A member can't act as their own guarantor.
+    InvalidGuarantor = 44,
+    
+    /// The cycle is still collecting contributions: its window is open, or
+    /// it is past its deadline but still taking late ones. Also stands in
+    /// for an underfunded payout made less than a full cycle after the last
+    /// one (`PayoutTooSoon`).
+    CycleNotReady = 45,
+    
+    /// This address has created too many groups in the last day.
+    CreationRateLimited = 46,
+    
+    /// The payout order must list every member exactly once.
+    InvalidPayoutOrder = 47,
+    
+    /// There is no overpaid credit to withdraw.
+    NoCredit = 48,
+    
+    /// The member hasn't approved the contract to draw enough of their tokens.
+    InsufficientAllowance = 49,
+    
+    /// The current cycle has an open dispute, so it can't be paid out.
+    CycleDisputed = 50,
 }
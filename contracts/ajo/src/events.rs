@@ -1,23 +1,150 @@
-use soroban_sdk::{symbol_short, Address, Env};
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
 
 /// Emit an event when a group is created
+///
+/// The token and its decimals travel with the event so indexers can format
+/// the amounts of every later event in the group.
 pub fn emit_group_created(
     env: &Env,
     group_id: u64,
     creator: &Address,
     contribution_amount: i128,
     max_members: u32,
+    token: &Address,
+    decimals: u32,
 ) {
     let topics = (symbol_short!("created"), group_id);
-    env.events().publish(topics, (creator, contribution_amount, max_members));
+    let timestamp = env.ledger().timestamp();
+    env.events().publish(
+        topics,
+        (creator, contribution_amount, max_members, timestamp, token, decimals),
+    );
 }
 
 /// Emit an event when a member joins a group
-pub fn emit_member_joined(env: &Env, group_id: u64, member: &Address) {
+///
+/// `member_count` is the group's size after the join, so progress towards
+/// a full group can be read straight off the event stream.
+pub fn emit_member_joined(env: &Env, group_id: u64, member: &Address, member_count: u32) {
     let topics = (symbol_short!("joined"), group_id);
+    env.events().publish(topics, (member, member_count, env.ledger().timestamp()));
+}
+
+/// Emit an event when a member joins on another member's referral
+pub fn emit_referral(env: &Env, group_id: u64, member: &Address, referrer: &Address) {
+    let topics = (symbol_short!("referral"), group_id);
+    env.events().publish(topics, (member, referrer));
+}
+
+/// Emit an event when a referrer is paid for a member who stayed to the end
+pub fn emit_referral_bonus(
+    env: &Env,
+    group_id: u64,
+    referrer: &Address,
+    member: &Address,
+    amount: i128,
+) {
+    let topics = (symbol_short!("refbonus"), group_id);
+    env.events().publish(topics, (referrer, member, amount));
+}
+
+/// Emit an event when the creator invites an address to a private group
+pub fn emit_member_invited(env: &Env, group_id: u64, invitee: &Address) {
+    let topics = (symbol_short!("invited"), group_id);
+    env.events().publish(topics, invitee);
+}
+
+/// Emit an event when a member leaves a group
+pub fn emit_member_left(env: &Env, group_id: u64, member: &Address, refunded: i128) {
+    let topics = (symbol_short!("left"), group_id);
+    env.events().publish(topics, (member, refunded));
+}
+
+/// Emit an event when the creator removes a member from a group
+pub fn emit_member_kicked(env: &Env, group_id: u64, member: &Address) {
+    let topics = (symbol_short!("kicked"), group_id);
+    env.events().publish(topics, member);
+}
+
+/// Emit an event when the creator puts a new cycle duration to a vote
+pub fn emit_duration_proposed(env: &Env, group_id: u64, creator: &Address, duration: u64) {
+    let topics = (symbol_short!("durprop"), group_id);
+    env.events().publish(topics, (creator, duration));
+}
+
+/// Emit an event when a member votes on a proposed cycle duration
+pub fn emit_duration_voted(env: &Env, group_id: u64, member: &Address, approve: bool) {
+    let topics = (symbol_short!("durvote"), group_id);
+    env.events().publish(topics, (member, approve));
+}
+
+/// Emit an event when a majority approves a new cycle duration
+pub fn emit_duration_changed(env: &Env, group_id: u64, duration: u64) {
+    let topics = (symbol_short!("duration"), group_id);
+    env.events().publish(topics, duration);
+}
+
+/// Emit an event when a member withdraws from an abandoned group
+pub fn emit_member_reclaimed(env: &Env, group_id: u64, member: &Address) {
+    let topics = (symbol_short!("reclaim"), group_id);
     env.events().publish(topics, member);
 }
 
+/// Emit an event when a group fills up and its first cycle begins
+pub fn emit_group_started(env: &Env, group_id: u64, member_count: u32, start_time: u64) {
+    let topics = (symbol_short!("started"), group_id);
+    env.events().publish(topics, (member_count, start_time));
+}
+
+/// Emit an event when a member deposits collateral
+pub fn emit_collateral_deposited(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let topics = (symbol_short!("collat"), group_id);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when a defaulter's collateral is slashed into the payout
+pub fn emit_collateral_slashed(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+    cycle: u32,
+    amount: i128,
+) {
+    let topics = (symbol_short!("slashed"), group_id, cycle);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when a member's missed contribution is recorded as a default
+pub fn emit_member_defaulted(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+    cycle: u32,
+    default_count: u32,
+) {
+    let topics = (symbol_short!("default"), group_id, cycle);
+    env.events().publish(topics, (member, default_count));
+}
+
+/// Emit an event when a guarantor covers a member's missed contribution
+pub fn emit_guarantor_covered(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+    guarantor: &Address,
+    cycle: u32,
+    amount: i128,
+) {
+    let topics = (symbol_short!("guaranty"), group_id, cycle);
+    env.events().publish(topics, (member, guarantor, amount));
+}
+
+/// Emit an event when a member's remaining collateral is returned
+pub fn emit_collateral_refunded(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let topics = (symbol_short!("colrefund"), group_id);
+    env.events().publish(topics, (member, amount));
+}
+
 /// Emit an event when a member contributes
 pub fn emit_contribution_made(
     env: &Env,
@@ -27,7 +154,20 @@ pub fn emit_contribution_made(
     amount: i128,
 ) {
     let topics = (symbol_short!("contrib"), group_id, cycle);
-    env.events().publish(topics, (member, amount));
+    env.events().publish(topics, (member, amount, env.ledger().timestamp()));
+}
+
+/// Emit an event when the last contribution a cycle needs comes in
+pub fn emit_cycle_ready(env: &Env, group_id: u64, cycle: u32) {
+    let topics = (symbol_short!("ready"), group_id, cycle);
+    env.events().publish(topics, ());
+}
+
+/// Emit an event when a cycle opens, listing the members who asked for a
+/// reminder and still owe their contribution
+pub fn emit_reminders(env: &Env, group_id: u64, cycle: u32, members: &Vec<Address>) {
+    let topics = (symbol_short!("remind"), group_id, cycle);
+    env.events().publish(topics, members.clone());
 }
 
 /// Emit an event when a payout is executed
@@ -39,29 +179,201 @@ pub fn emit_payout_executed(
     amount: i128,
 ) {
     let topics = (symbol_short!("payout"), group_id, cycle);
-    env.events().publish(topics, (recipient, amount));
+    env.events().publish(topics, (recipient, amount, env.ledger().timestamp()));
 }
 
 /// Emit an event when a group completes all cycles
 pub fn emit_group_completed(env: &Env, group_id: u64) {
     let topics = (symbol_short!("complete"), group_id);
-    env.events().publish(topics, ());
+    env.events().publish(topics, env.ledger().timestamp());
 }
 
 /// Emit an event when a cycle advances
+#[allow(dead_code)]
 pub fn emit_cycle_advanced(env: &Env, group_id: u64, new_cycle: u32, cycle_start_time: u64) {
     let topics = (symbol_short!("cycle"), group_id);
     env.events().publish(topics, (new_cycle, cycle_start_time));
 }
 
 /// Emit an event when a group is cancelled by its creator
-pub fn emit_group_cancelled(
+pub fn emit_group_cancelled(env: &Env, group_id: u64, creator: &Address, cycle: u32) {
+    let topics = (symbol_short!("cancel"), group_id);
+    env.events().publish(topics, (creator, cycle));
+}
+
+/// Emit an event when an unfilled group is expired after its fill deadline
+pub fn emit_group_expired(env: &Env, group_id: u64, member_count: u32, fill_deadline: u64) {
+    let topics = (symbol_short!("expired"), group_id);
+    env.events().publish(topics, (member_count, fill_deadline));
+}
+
+/// Emit an event when a finished group's records are deleted
+pub fn emit_group_swept(env: &Env, group_id: u64, entries_removed: u32) {
+    let topics = (symbol_short!("swept"), group_id);
+    env.events().publish(topics, entries_removed);
+}
+
+/// Emit an event when a creator pauses their group
+pub fn emit_group_paused(env: &Env, group_id: u64, creator: &Address) {
+    let topics = (symbol_short!("paused"), group_id);
+    env.events().publish(topics, creator);
+}
+
+/// Emit an event when a creator resumes their paused group
+pub fn emit_group_resumed(env: &Env, group_id: u64, creator: &Address) {
+    let topics = (symbol_short!("resumed"), group_id);
+    env.events().publish(topics, creator);
+}
+
+/// Emit an event when a member pays a late fee on their contribution
+pub fn emit_late_penalty(env: &Env, group_id: u64, member: &Address, cycle: u32, amount: i128) {
+    let topics = (symbol_short!("late"), group_id, cycle);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when a member receives their share of the penalty pool
+pub fn emit_penalty_share(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let topics = (symbol_short!("penshare"), group_id);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when a member receives their share of rounding dust
+pub fn emit_dust_share(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let topics = (symbol_short!("dust"), group_id);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when a member who never defaulted receives a completion bonus
+pub fn emit_completion_bonus(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let topics = (symbol_short!("bonus"), group_id);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event with a member's net position once a group completes
+///
+/// `net` is what the member received less what they paid in, collateral
+/// taken to cover missed cycles included.
+pub fn emit_member_settled(env: &Env, group_id: u64, member: &Address, net: i128) {
+    let topics = (symbol_short!("settle"), group_id);
+    env.events().publish(topics, (member, net));
+}
+
+/// Emit an event when the creator sets a member's agreed contribution
+pub fn emit_member_contribution_set(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let topics = (symbol_short!("amount"), group_id);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when the creator changes a group's member cap
+pub fn emit_max_members_set(env: &Env, group_id: u64, creator: &Address, max_members: u32) {
+    let topics = (symbol_short!("maxset"), group_id);
+    env.events().publish(topics, (creator, max_members));
+}
+
+/// Emit an event when a member withdraws what they overpaid
+pub fn emit_credit_withdrawn(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let topics = (symbol_short!("credit"), group_id);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when a member disputes a cycle
+pub fn emit_dispute_raised(env: &Env, group_id: u64, cycle: u32, member: &Address, reason: &Symbol) {
+    let topics = (symbol_short!("dispute"), group_id, cycle);
+    env.events().publish(topics, (member, reason));
+}
+
+/// Emit an event when the creator resolves a cycle's dispute
+pub fn emit_dispute_resolved(env: &Env, group_id: u64, cycle: u32, creator: &Address) {
+    let topics = (symbol_short!("resolved"), group_id, cycle);
+    env.events().publish(topics, creator);
+}
+
+/// Emit an event when a member is refunded their contribution
+pub fn emit_refund(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let topics = (symbol_short!("refund"), group_id);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when a member places an auction bid
+pub fn emit_bid_placed(env: &Env, group_id: u64, member: &Address, cycle: u32, amount: i128) {
+    let topics = (symbol_short!("bid"), group_id, cycle);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when a member receives their share of the winning bid
+pub fn emit_bid_share(env: &Env, group_id: u64, member: &Address, cycle: u32, amount: i128) {
+    let topics = (symbol_short!("bidshare"), group_id, cycle);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when the creator takes a commission on a payout
+pub fn emit_commission_paid(
     env: &Env,
     group_id: u64,
     creator: &Address,
-    member_count: u32,
-    refund_per_member: i128,
+    cycle: u32,
+    amount: i128,
 ) {
-    let topics = (symbol_short!("cancel"), group_id);
-    env.events().publish(topics, (creator, member_count, refund_per_member));
+    let topics = (symbol_short!("commiss"), group_id, cycle);
+    env.events().publish(topics, (creator, amount));
+}
+
+/// Emit an event when the platform fee is taken from a payout
+pub fn emit_platform_fee_paid(
+    env: &Env,
+    group_id: u64,
+    collector: &Address,
+    cycle: u32,
+    amount: i128,
+) {
+    let topics = (symbol_short!("platfee"), group_id, cycle);
+    env.events().publish(topics, (collector, amount));
+}
+
+/// Emit an event when a group's payout order is shuffled
+pub fn emit_order_shuffled(env: &Env, group_id: u64, payout_order: &Vec<Address>) {
+    let topics = (symbol_short!("shuffled"), group_id);
+    env.events().publish(topics, payout_order.clone());
+}
+
+/// Emit an event when the creator fixes a custom payout order
+pub fn emit_order_set(env: &Env, group_id: u64, payout_order: &Vec<Address>) {
+    let topics = (symbol_short!("order"), group_id);
+    env.events().publish(topics, payout_order.clone());
+}
+
+/// Emit an event when two members swap payout positions
+pub fn emit_positions_swapped(env: &Env, group_id: u64, member_a: &Address, member_b: &Address) {
+    let topics = (symbol_short!("swap"), group_id);
+    env.events().publish(topics, (member_a, member_b));
+}
+
+/// Emit an event when a group's creator role changes hands
+pub fn emit_creator_transferred(
+    env: &Env,
+    group_id: u64,
+    old_creator: &Address,
+    new_creator: &Address,
+) {
+    let topics = (symbol_short!("xfercrtr"), group_id);
+    env.events().publish(topics, (old_creator, new_creator));
+}
+
+/// Emit an event when a member's waived pool rolls into the next payout
+pub fn emit_payout_waived(env: &Env, group_id: u64, member: &Address, cycle: u32, amount: i128) {
+    let topics = (symbol_short!("waived"), group_id, cycle);
+    env.events().publish(topics, (member, amount));
+}
+
+/// Emit an event when a payout is sent to a member's delegated address
+pub fn emit_payout_delegated(
+    env: &Env,
+    group_id: u64,
+    member: &Address,
+    destination: &Address,
+    cycle: u32,
+    amount: i128,
+) {
+    let topics = (symbol_short!("paydest"), group_id, cycle);
+    env.events().publish(topics, (member, destination, amount));
 }
@@ -21,3 +21,7 @@ mod utils;
 pub use contract::AjoContract;
 pub use contract::AjoContractClient;
 pub use errors::AjoError;
+pub use types::{
+    ContractStats, ContributionPolicy, ContributionRecord, Dispute, GroupConfig, GroupMetadata,
+    GroupSnapshot, GroupState, GroupTemplate, GroupView, PayoutMode, PriceData,
+};
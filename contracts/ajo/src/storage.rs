@@ -1,35 +1,177 @@
-use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec};
+
+/// Ledgers per day, assuming ~5 second ledger close times
+const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Layout version of the data this build reads and writes
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// How far (in ledgers) entries are extended when bumped: ~30 days
+pub const LEDGER_BUMP: u32 = 30 * DAY_IN_LEDGERS;
+
+/// Entries whose TTL falls below this many ledgers get bumped on write
+pub const LEDGER_THRESHOLD: u32 = LEDGER_BUMP - DAY_IN_LEDGERS;
 
 /// Storage keys for the Ajo contract
+#[allow(dead_code)]
 pub enum StorageKey {
     /// Contract admin
     Admin,
     
+    /// Global pause flag: Paused -> bool
+    Paused,
+    
+    /// Whether new groups may be created: CreationEnabled -> bool
+    CreationEnabled,
+    
+    /// Operator's cut of every payout: PlatformFee -> (u32, Address)
+    PlatformFee,
+    
+    /// Layout version of stored data: SchemaVersion -> u32
+    SchemaVersion,
+    
+    /// Next group ID a running migration will look at: MigrationCursor -> u64
+    MigrationCursor,
+    
+    /// Reentrancy guard, held while funds move: Lock -> bool
+    Lock,
+    
     /// Counter for group IDs
     GroupCounter,
     
+    /// Number of groups still running: ActiveGroups -> u64
+    ActiveGroups,
+    
+    /// Number of groups that finished every cycle: CompletedGroups -> u64
+    CompletedGroups,
+    
     /// Group data: Group(group_id) -> Group
     Group(u64),
     
     /// Contribution record: Contribution(group_id, cycle, member) -> bool
     Contribution(u64, u32, Address),
     
-    /// Payout record: Payout(group_id, member) -> bool
+    /// Payout record: Payout(group_id, member) -> u32 (the cycle paid)
     PayoutReceived(u64, Address),
+    
+    /// Auction bid: Bid(group_id, cycle, member) -> i128
+    Bid(u64, u32, Address),
+    
+    /// Missed contributions across all groups: Defaults(member) -> u32
+    Defaults(Address),
+    
+    /// Missed contributions within one group: GroupDefaults(group_id, member) -> u32
+    GroupDefaults(u64, Address),
+    
+    /// Group display metadata: Metadata(group_id) -> GroupMetadata
+    Metadata(u64),
+    
+    /// Delegated payout address: PayoutDestination(group_id, member) -> Address
+    PayoutDestination(u64, Address),
+    
+    /// Collateral held for a member: Collateral(group_id, member) -> i128
+    Collateral(u64, Address),
+    
+    /// Groups a member belongs to: MemberGroups(member) -> Vec<u64>
+    MemberGroups(Address),
+    
+    /// Pending invite to a private group: Invite(group_id, invitee) -> bool
+    Invite(u64, Address),
+    
+    /// Backer of a member's contributions: Guarantor(group_id, member) -> Address
+    Guarantor(u64, Address),
+    
+    /// Recent group creation times: Creations(creator) -> Vec<u64>
+    Creations(Address),
+    
+    /// Overpaid amount owed back to a member: Credit(group_id, member) -> i128
+    Credit(u64, Address),
+    
+    /// Open dispute on a cycle: Dispute(group_id, cycle) -> Dispute
+    Dispute(u64, u32),
+    
+    /// Whether a cycle's payout went out: CyclePaid(group_id, cycle) -> bool
+    CyclePaid(u64, u32),
+    
+    /// Member giving up their payout: Waived(group_id, member) -> bool
+    Waived(u64, Address),
+    
+    /// Waived pool carried to the next payout: Rollover(group_id) -> i128
+    Rollover(u64),
+    
+    /// Money each member has paid in and received: Flows(group_id) -> Map<Address, (i128, i128)>
+    Flows(u64),
+    
+    /// Saved group parameters: Template(creator, template_id) -> GroupTemplate
+    Template(Address, u32),
+    
+    /// When a group was last stored: Activity(group_id) -> u64
+    Activity(u64),
+    
+    /// Cycle duration put to a member vote: DurationProposal(group_id) -> u64
+    DurationProposal(u64),
+    
+    /// A member's vote on the proposed duration: DurationVote(group_id, member) -> bool
+    DurationVote(u64, Address),
+    
+    /// Group a creator made with a retry key: IdempotencyKey(creator, key) -> group_id
+    IdempotencyKey(Address, BytesN<32>),
+    
+    /// Members who want contribution reminders: Reminders(group_id) -> Vec<Address>
+    Reminders(u64),
+    
+    /// Member who referred a joiner: Referral(group_id, member) -> Address
+    Referral(u64, Address),
+    
+    /// Addresses that left or were removed from a group: Departed(group_id) -> Vec<Address>
+    Departed(u64),
 }
 
+#[allow(dead_code)]
 impl StorageKey {
     /// Convert storage key to Symbol for use with Soroban storage
-    pub fn to_symbol(&self, env: &Env) -> Symbol {
+    pub fn to_symbol(&self, _env: &Env) -> Symbol {
         match self {
             StorageKey::Admin => symbol_short!("ADMIN"),
+            StorageKey::Paused => symbol_short!("PAUSED"),
+            StorageKey::CreationEnabled => symbol_short!("CREATION"),
+            StorageKey::PlatformFee => symbol_short!("PLATFEE"),
+            StorageKey::SchemaVersion => symbol_short!("SCHEMA"),
+            StorageKey::MigrationCursor => symbol_short!("MIGRATED"),
+            StorageKey::Lock => symbol_short!("LOCK"),
             StorageKey::GroupCounter => symbol_short!("GCOUNTER"),
-            StorageKey::Group(id) => {
+            StorageKey::ActiveGroups => symbol_short!("GACTIVE"),
+            StorageKey::CompletedGroups => symbol_short!("GDONE"),
+            StorageKey::Group(_) => {
                 // For complex keys, we use a tuple-like approach
                 symbol_short!("GROUP")
             }
             StorageKey::Contribution(_, _, _) => symbol_short!("CONTRIB"),
             StorageKey::PayoutReceived(_, _) => symbol_short!("PAYOUT"),
+            StorageKey::Bid(_, _, _) => symbol_short!("BID"),
+            StorageKey::Defaults(_) => symbol_short!("DEFAULTS"),
+            StorageKey::GroupDefaults(_, _) => symbol_short!("GDEFAULTS"),
+            StorageKey::Metadata(_) => symbol_short!("META"),
+            StorageKey::PayoutDestination(_, _) => symbol_short!("PAYDEST"),
+            StorageKey::Collateral(_, _) => symbol_short!("COLLAT"),
+            StorageKey::MemberGroups(_) => symbol_short!("MGROUPS"),
+            StorageKey::Invite(_, _) => symbol_short!("INVITE"),
+            StorageKey::Guarantor(_, _) => symbol_short!("GUARANTOR"),
+            StorageKey::Creations(_) => symbol_short!("CREATIONS"),
+            StorageKey::Credit(_, _) => symbol_short!("CREDIT"),
+            StorageKey::Dispute(_, _) => symbol_short!("DISPUTE"),
+            StorageKey::CyclePaid(_, _) => symbol_short!("CYCPAID"),
+            StorageKey::Waived(_, _) => symbol_short!("WAIVED"),
+            StorageKey::Rollover(_) => symbol_short!("ROLLOVER"),
+            StorageKey::Flows(_) => symbol_short!("FLOWS"),
+            StorageKey::Template(_, _) => symbol_short!("TEMPLATE"),
+            StorageKey::Activity(_) => symbol_short!("ACTIVITY"),
+            StorageKey::DurationProposal(_) => symbol_short!("DURPROP"),
+            StorageKey::DurationVote(_, _) => symbol_short!("DURVOTE"),
+            StorageKey::IdempotencyKey(_, _) => symbol_short!("IDEMKEY"),
+            StorageKey::Reminders(_) => symbol_short!("REMIND"),
+            StorageKey::Referral(_, _) => symbol_short!("REFERRAL"),
+            StorageKey::Departed(_) => symbol_short!("DEPARTED"),
         }
     }
 }
@@ -40,13 +182,54 @@ pub fn get_next_group_id(env: &Env) -> u64 {
     let current: u64 = env.storage().instance().get(&key).unwrap_or(0);
     let next = current + 1;
     env.storage().instance().set(&key, &next);
+    env.storage().instance().extend_ttl(LEDGER_THRESHOLD, LEDGER_BUMP);
     next
 }
 
+/// Get the highest group ID issued so far
+pub fn get_group_count(env: &Env) -> u64 {
+    let key = symbol_short!("GCOUNTER");
+    env.storage().instance().get(&key).unwrap_or(0)
+}
+
+/// Get the number of groups still running
+pub fn get_active_group_count(env: &Env) -> u64 {
+    env.storage().instance().get(&symbol_short!("GACTIVE")).unwrap_or(0)
+}
+
+/// Get the number of groups that finished every cycle
+pub fn get_completed_group_count(env: &Env) -> u64 {
+    env.storage().instance().get(&symbol_short!("GDONE")).unwrap_or(0)
+}
+
+/// Count a newly created group as active
+pub fn record_group_created(env: &Env) {
+    let active = get_active_group_count(env) + 1;
+    env.storage().instance().set(&symbol_short!("GACTIVE"), &active);
+}
+
+/// Move a group from active to completed
+pub fn record_group_completed(env: &Env) {
+    let active = get_active_group_count(env).saturating_sub(1);
+    let completed = get_completed_group_count(env) + 1;
+    env.storage().instance().set(&symbol_short!("GACTIVE"), &active);
+    env.storage().instance().set(&symbol_short!("GDONE"), &completed);
+}
+
+/// Stop counting a cancelled or expired group as active
+pub fn record_group_closed(env: &Env) {
+    let active = get_active_group_count(env).saturating_sub(1);
+    env.storage().instance().set(&symbol_short!("GACTIVE"), &active);
+}
+
 /// Store a group in persistent storage
+///
+/// Also records the current time as the group's last activity.
 pub fn store_group(env: &Env, group_id: u64, group: &crate::types::Group) {
     let key = (symbol_short!("GROUP"), group_id);
     env.storage().persistent().set(&key, group);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+    set_last_activity(env, group_id, env.ledger().timestamp());
 }
 
 /// Get a group from storage
@@ -55,36 +238,536 @@ pub fn get_group(env: &Env, group_id: u64) -> Option<crate::types::Group> {
     env.storage().persistent().get(&key)
 }
 
+/// Get a group stored under the version 1 layout
+///
+/// Returns `None` if there is no entry or it isn't in the legacy layout.
+pub fn get_legacy_group(env: &Env, group_id: u64) -> Option<crate::types::LegacyGroupV1> {
+    let key = (symbol_short!("GROUP"), group_id);
+    let raw: Val = env.storage().persistent().get(&key)?;
+    crate::types::LegacyGroupV1::try_from_val(env, &raw).ok()
+}
+
 /// Remove a group from storage
 pub fn remove_group(env: &Env, group_id: u64) {
     let key = (symbol_short!("GROUP"), group_id);
     env.storage().persistent().remove(&key);
 }
 
+/// Store a group's display metadata
+pub fn store_metadata(env: &Env, group_id: u64, metadata: &crate::types::GroupMetadata) {
+    let key = (symbol_short!("META"), group_id);
+    env.storage().persistent().set(&key, metadata);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get a group's display metadata
+pub fn get_metadata(env: &Env, group_id: u64) -> Option<crate::types::GroupMetadata> {
+    let key = (symbol_short!("META"), group_id);
+    env.storage().persistent().get(&key)
+}
+
 /// Store a contribution record
-pub fn store_contribution(env: &Env, group_id: u64, cycle: u32, member: &Address, paid: bool) {
+pub fn store_contribution(
+    env: &Env,
+    group_id: u64,
+    cycle: u32,
+    member: &Address,
+    record: &crate::types::ContributionRecord,
+) {
     let key = (symbol_short!("CONTRIB"), group_id, cycle, member);
-    env.storage().persistent().set(&key, &paid);
+    env.storage().persistent().set(&key, record);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Clear a member's contribution for a cycle, e.g. once it is refunded
+pub fn remove_contribution(env: &Env, group_id: u64, cycle: u32, member: &Address) {
+    let key = (symbol_short!("CONTRIB"), group_id, cycle, member);
+    env.storage().persistent().remove(&key);
 }
 
 /// Check if a member has contributed for a cycle
+///
+/// Entries written before receipts were kept hold a plain flag instead.
 pub fn has_contributed(env: &Env, group_id: u64, cycle: u32, member: &Address) -> bool {
     let key = (symbol_short!("CONTRIB"), group_id, cycle, member);
-    env.storage().persistent().get(&key).unwrap_or(false)
+    let raw: Option<Val> = env.storage().persistent().get(&key);
+    raw.map(|raw| bool::try_from_val(env, &raw).unwrap_or(true))
+        .unwrap_or(false)
 }
 
-/// Mark a member as having received their payout
-pub fn mark_payout_received(env: &Env, group_id: u64, member: &Address) {
+/// Get the receipt for a member's contribution to a cycle
+///
+/// Returns `None` if they haven't contributed, or if the entry predates
+/// receipts and holds only a flag.
+pub fn get_contribution_record(
+    env: &Env,
+    group_id: u64,
+    cycle: u32,
+    member: &Address,
+) -> Option<crate::types::ContributionRecord> {
+    let key = (symbol_short!("CONTRIB"), group_id, cycle, member);
+    let raw: Val = env.storage().persistent().get(&key)?;
+    crate::types::ContributionRecord::try_from_val(env, &raw).ok()
+}
+
+/// Mark a member as having received the payout for a cycle
+pub fn mark_payout_received(env: &Env, group_id: u64, member: &Address, cycle: u32) {
     let key = (symbol_short!("PAYOUT"), group_id, member);
-    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().set(&key, &cycle);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
 }
 
 /// Check if a member has received their payout
+///
+/// Entries written before the cycle was kept hold a plain flag instead.
 pub fn has_received_payout(env: &Env, group_id: u64, member: &Address) -> bool {
     let key = (symbol_short!("PAYOUT"), group_id, member);
+    let raw: Option<Val> = env.storage().persistent().get(&key);
+    raw.map(|raw| bool::try_from_val(env, &raw).unwrap_or(true))
+        .unwrap_or(false)
+}
+
+/// Get the cycle whose payout a member received
+///
+/// Returns `None` if they haven't been paid, or if the entry predates
+/// cycles being kept and holds only a flag.
+pub fn get_payout_cycle(env: &Env, group_id: u64, member: &Address) -> Option<u32> {
+    let key = (symbol_short!("PAYOUT"), group_id, member);
+    let raw: Val = env.storage().persistent().get(&key)?;
+    u32::try_from_val(env, &raw).ok()
+}
+
+/// Store a member's auction bid for a cycle
+pub fn store_bid(env: &Env, group_id: u64, cycle: u32, member: &Address, amount: i128) {
+    let key = (symbol_short!("BID"), group_id, cycle, member);
+    env.storage().persistent().set(&key, &amount);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get a member's auction bid for a cycle (0 if none was placed)
+pub fn get_bid(env: &Env, group_id: u64, cycle: u32, member: &Address) -> i128 {
+    let key = (symbol_short!("BID"), group_id, cycle, member);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Record a missed contribution for a member and return their new default count
+pub fn increment_default(env: &Env, member: &Address) -> u32 {
+    let key = (symbol_short!("DEFAULTS"), member);
+    let count: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+    let next = count + 1;
+    env.storage().persistent().set(&key, &next);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+    next
+}
+
+/// Record a missed contribution for a member within a group
+pub fn increment_group_default(env: &Env, group_id: u64, member: &Address) {
+    let key = (symbol_short!("GDEFAULTS"), group_id, member);
+    let count: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(count + 1));
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get how many contributions a member has missed within a group
+pub fn get_group_default_count(env: &Env, group_id: u64, member: &Address) -> u32 {
+    let key = (symbol_short!("GDEFAULTS"), group_id, member);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Get how many contributions a member has missed across all groups
+pub fn get_default_count(env: &Env, member: &Address) -> u32 {
+    let key = (symbol_short!("DEFAULTS"), member);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Store where a member's payout should be sent
+pub fn store_payout_destination(env: &Env, group_id: u64, member: &Address, destination: &Address) {
+    let key = (symbol_short!("PAYDEST"), group_id, member);
+    env.storage().persistent().set(&key, destination);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Remove a member's delegated payout address
+pub fn remove_payout_destination(env: &Env, group_id: u64, member: &Address) {
+    let key = (symbol_short!("PAYDEST"), group_id, member);
+    env.storage().persistent().remove(&key);
+}
+
+/// Get a member's delegated payout address, if any
+pub fn get_payout_destination(env: &Env, group_id: u64, member: &Address) -> Option<Address> {
+    let key = (symbol_short!("PAYDEST"), group_id, member);
+    env.storage().persistent().get(&key)
+}
+
+/// Store who covers a member's missed contributions
+pub fn store_guarantor(env: &Env, group_id: u64, member: &Address, guarantor: &Address) {
+    let key = (symbol_short!("GUARANTOR"), group_id, member);
+    env.storage().persistent().set(&key, guarantor);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get who covers a member's missed contributions, if anyone
+pub fn get_guarantor(env: &Env, group_id: u64, member: &Address) -> Option<Address> {
+    let key = (symbol_short!("GUARANTOR"), group_id, member);
+    env.storage().persistent().get(&key)
+}
+
+/// Store an open dispute on a cycle
+pub fn store_dispute(env: &Env, group_id: u64, cycle: u32, dispute: &crate::types::Dispute) {
+    let key = (symbol_short!("DISPUTE"), group_id, cycle);
+    env.storage().persistent().set(&key, dispute);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get the open dispute on a cycle, if any
+pub fn get_dispute(env: &Env, group_id: u64, cycle: u32) -> Option<crate::types::Dispute> {
+    let key = (symbol_short!("DISPUTE"), group_id, cycle);
+    env.storage().persistent().get(&key)
+}
+
+/// Record that a cycle's payout has gone out
+pub fn mark_cycle_paid(env: &Env, group_id: u64, cycle: u32) {
+    let key = (symbol_short!("CYCPAID"), group_id, cycle);
+    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Check whether a cycle's payout has gone out
+pub fn is_cycle_paid(env: &Env, group_id: u64, cycle: u32) -> bool {
+    let key = (symbol_short!("CYCPAID"), group_id, cycle);
+    env.storage().persistent().get(&key).unwrap_or(false)
+}
+
+/// Clear a cycle's dispute once it is resolved
+pub fn remove_dispute(env: &Env, group_id: u64, cycle: u32) {
+    let key = (symbol_short!("DISPUTE"), group_id, cycle);
+    env.storage().persistent().remove(&key);
+}
+
+/// Store how much collateral is held for a member
+pub fn store_collateral(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let key = (symbol_short!("COLLAT"), group_id, member);
+    env.storage().persistent().set(&key, &amount);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get how much collateral is held for a member
+pub fn get_collateral(env: &Env, group_id: u64, member: &Address) -> i128 {
+    let key = (symbol_short!("COLLAT"), group_id, member);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Store how much a member overpaid into a group
+pub fn store_credit(env: &Env, group_id: u64, member: &Address, amount: i128) {
+    let key = (symbol_short!("CREDIT"), group_id, member);
+    env.storage().persistent().set(&key, &amount);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get how much a member overpaid into a group (0 if nothing)
+pub fn get_credit(env: &Env, group_id: u64, member: &Address) -> i128 {
+    let key = (symbol_short!("CREDIT"), group_id, member);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Mark a member as waiving their payout
+pub fn mark_payout_waived(env: &Env, group_id: u64, member: &Address) {
+    let key = (symbol_short!("WAIVED"), group_id, member);
+    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Check if a member has waived their payout
+pub fn has_waived_payout(env: &Env, group_id: u64, member: &Address) -> bool {
+    let key = (symbol_short!("WAIVED"), group_id, member);
     env.storage().persistent().get(&key).unwrap_or(false)
 }
 
+/// Store the waived pool carried into a group's next payout
+pub fn store_rollover(env: &Env, group_id: u64, amount: i128) {
+    let key = (symbol_short!("ROLLOVER"), group_id);
+    env.storage().persistent().set(&key, &amount);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get the waived pool carried into a group's next payout (0 if nothing)
+pub fn get_rollover(env: &Env, group_id: u64) -> i128 {
+    let key = (symbol_short!("ROLLOVER"), group_id);
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set when a group was last changed
+pub fn set_last_activity(env: &Env, group_id: u64, timestamp: u64) {
+    let key = (symbol_short!("ACTIVITY"), group_id);
+    env.storage().persistent().set(&key, &timestamp);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get when a group was last changed, if recorded
+pub fn get_last_activity(env: &Env, group_id: u64) -> Option<u64> {
+    let key = (symbol_short!("ACTIVITY"), group_id);
+    env.storage().persistent().get(&key)
+}
+
+/// Store what each member has paid into and received from a group's payouts
+pub fn store_member_flows(env: &Env, group_id: u64, flows: &Map<Address, (i128, i128)>) {
+    let key = (symbol_short!("FLOWS"), group_id);
+    env.storage().persistent().set(&key, flows);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get what each member has paid into and received from a group's payouts
+pub fn get_member_flows(env: &Env, group_id: u64) -> Map<Address, (i128, i128)> {
+    let key = (symbol_short!("FLOWS"), group_id);
+    env.storage().persistent().get(&key).unwrap_or(Map::new(env))
+}
+
+/// Store a creator's saved group template
+pub fn store_template(
+    env: &Env,
+    creator: &Address,
+    template_id: u32,
+    template: &crate::types::GroupTemplate,
+) {
+    let key = (symbol_short!("TEMPLATE"), creator, template_id);
+    env.storage().persistent().set(&key, template);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get a creator's saved group template, if any
+pub fn get_template(
+    env: &Env,
+    creator: &Address,
+    template_id: u32,
+) -> Option<crate::types::GroupTemplate> {
+    let key = (symbol_short!("TEMPLATE"), creator, template_id);
+    env.storage().persistent().get(&key)
+}
+
+/// Store the cycle duration a group is voting on
+pub fn store_duration_proposal(env: &Env, group_id: u64, duration: u64) {
+    let key = (symbol_short!("DURPROP"), group_id);
+    env.storage().persistent().set(&key, &duration);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get the cycle duration a group is voting on, if any
+pub fn get_duration_proposal(env: &Env, group_id: u64) -> Option<u64> {
+    let key = (symbol_short!("DURPROP"), group_id);
+    env.storage().persistent().get(&key)
+}
+
+/// Clear a group's duration proposal once it is settled or replaced
+pub fn remove_duration_proposal(env: &Env, group_id: u64) {
+    let key = (symbol_short!("DURPROP"), group_id);
+    env.storage().persistent().remove(&key);
+}
+
+/// Record a member's vote on the proposed duration
+pub fn store_duration_vote(env: &Env, group_id: u64, member: &Address, approve: bool) {
+    let key = (symbol_short!("DURVOTE"), group_id, member);
+    env.storage().persistent().set(&key, &approve);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get a member's vote on the proposed duration, if they have voted
+pub fn get_duration_vote(env: &Env, group_id: u64, member: &Address) -> Option<bool> {
+    let key = (symbol_short!("DURVOTE"), group_id, member);
+    env.storage().persistent().get(&key)
+}
+
+/// Clear a member's vote on the proposed duration
+pub fn remove_duration_vote(env: &Env, group_id: u64, member: &Address) {
+    let key = (symbol_short!("DURVOTE"), group_id, member);
+    env.storage().persistent().remove(&key);
+}
+
+/// Remember which group a creator made with a retry key
+pub fn store_idempotency_key(
+    env: &Env,
+    creator: &Address,
+    retry_key: &BytesN<32>,
+    group_id: u64,
+) {
+    let key = (symbol_short!("IDEMKEY"), creator, retry_key.clone());
+    env.storage().persistent().set(&key, &group_id);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get the group a creator made with a retry key, if any
+pub fn get_idempotency_key(
+    env: &Env,
+    creator: &Address,
+    retry_key: &BytesN<32>,
+) -> Option<u64> {
+    let key = (symbol_short!("IDEMKEY"), creator, retry_key.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Get the members of a group who want contribution reminders
+pub fn get_reminders(env: &Env, group_id: u64) -> Vec<Address> {
+    let key = (symbol_short!("REMIND"), group_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Store the members of a group who want contribution reminders
+pub fn store_reminders(env: &Env, group_id: u64, members: &Vec<Address>) {
+    let key = (symbol_short!("REMIND"), group_id);
+    env.storage().persistent().set(&key, members);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Record the member who referred a joiner
+pub fn store_referral(env: &Env, group_id: u64, member: &Address, referrer: &Address) {
+    let key = (symbol_short!("REFERRAL"), group_id, member);
+    env.storage().persistent().set(&key, referrer);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get the member who referred a joiner, if any
+pub fn get_referral(env: &Env, group_id: u64, member: &Address) -> Option<Address> {
+    let key = (symbol_short!("REFERRAL"), group_id, member);
+    env.storage().persistent().get(&key)
+}
+
+/// Get the addresses that left or were removed from a group
+pub fn get_departed_members(env: &Env, group_id: u64) -> Vec<Address> {
+    let key = (symbol_short!("DEPARTED"), group_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Remember an address that left or was removed from a group, so its
+/// records can still be found once it is no longer a member
+pub fn add_departed_member(env: &Env, group_id: u64, member: &Address) {
+    let mut departed = get_departed_members(env, group_id);
+    if departed.contains(member) {
+        return;
+    }
+    departed.push_back(member.clone());
+    let key = (symbol_short!("DEPARTED"), group_id);
+    env.storage().persistent().set(&key, &departed);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Remove a persistent entry, reporting whether there was one
+fn remove_entry<K: IntoVal<Env, Val>>(env: &Env, key: &K) -> bool {
+    let storage = env.storage().persistent();
+    if !storage.has(key) {
+        return false;
+    }
+    storage.remove(key);
+    true
+}
+
+/// Remove everything stored for a group
+///
+/// Clears the group's group-wide records, leaving the group entry itself
+/// for `remove_group`, as well as every per-address
+/// record for `addresses`, and every per-cycle record for cycles 1 to
+/// `cycles`. Returns how many entries were removed.
+pub fn remove_group_records(
+    env: &Env,
+    group_id: u64,
+    addresses: &Vec<Address>,
+    cycles: u32,
+) -> u32 {
+    let mut removed = 0u32;
+    let mut count = |existed: bool| {
+        if existed {
+            removed += 1;
+        }
+    };
+    
+    for member in addresses.iter() {
+        for cycle in 1..=cycles {
+            count(remove_entry(env, &(symbol_short!("CONTRIB"), group_id, cycle, member.clone())));
+            count(remove_entry(env, &(symbol_short!("BID"), group_id, cycle, member.clone())));
+        }
+        for name in [
+            symbol_short!("PAYOUT"),
+            symbol_short!("GDEFAULTS"),
+            symbol_short!("PAYDEST"),
+            symbol_short!("COLLAT"),
+            symbol_short!("GUARANTOR"),
+            symbol_short!("CREDIT"),
+            symbol_short!("WAIVED"),
+            symbol_short!("DURVOTE"),
+            symbol_short!("INVITE"),
+            symbol_short!("REFERRAL"),
+        ] {
+            count(remove_entry(env, &(name, group_id, member.clone())));
+        }
+        remove_member_group(env, &member, group_id);
+    }
+    for cycle in 1..=cycles {
+        count(remove_entry(env, &(symbol_short!("DISPUTE"), group_id, cycle)));
+        count(remove_entry(env, &(symbol_short!("CYCPAID"), group_id, cycle)));
+    }
+    for name in [
+        symbol_short!("META"),
+        symbol_short!("ROLLOVER"),
+        symbol_short!("FLOWS"),
+        symbol_short!("ACTIVITY"),
+        symbol_short!("DURPROP"),
+        symbol_short!("REMIND"),
+        symbol_short!("DEPARTED"),
+    ] {
+        count(remove_entry(env, &(name, group_id)));
+    }
+    removed
+}
+
+/// Get the ids of every group a member belongs to
+pub fn get_member_groups(env: &Env, member: &Address) -> Vec<u64> {
+    let key = (symbol_short!("MGROUPS"), member);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Record that a member belongs to a group
+pub fn add_member_group(env: &Env, member: &Address, group_id: u64) {
+    let key = (symbol_short!("MGROUPS"), member);
+    let mut groups = get_member_groups(env, member);
+    groups.push_back(group_id);
+    env.storage().persistent().set(&key, &groups);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Drop a group from a member's list of groups
+pub fn remove_member_group(env: &Env, member: &Address, group_id: u64) {
+    let key = (symbol_short!("MGROUPS"), member);
+    let mut groups = get_member_groups(env, member);
+    if let Some(index) = groups.first_index_of(group_id) {
+        groups.remove(index);
+        env.storage().persistent().set(&key, &groups);
+    }
+}
+
+/// Record an invite to a private group
+pub fn store_invite(env: &Env, group_id: u64, invitee: &Address) {
+    let key = (symbol_short!("INVITE"), group_id, invitee);
+    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Use up an invite, returning whether one existed
+pub fn take_invite(env: &Env, group_id: u64, invitee: &Address) -> bool {
+    let key = (symbol_short!("INVITE"), group_id, invitee);
+    let invited = env.storage().persistent().get(&key).unwrap_or(false);
+    if invited {
+        env.storage().persistent().remove(&key);
+    }
+    invited
+}
+
 /// Get contribution status for all members in a cycle
 pub fn get_cycle_contributions(
     env: &Env,
@@ -102,6 +785,22 @@ pub fn get_cycle_contributions(
     results
 }
 
+/// Store when an address recently created groups
+pub fn store_creation_times(env: &Env, creator: &Address, times: &Vec<u64>) {
+    let key = (symbol_short!("CREATIONS"), creator);
+    env.storage().persistent().set(&key, times);
+    env.storage().persistent().extend_ttl(&key, LEDGER_THRESHOLD, LEDGER_BUMP);
+}
+
+/// Get when an address recently created groups, oldest first
+pub fn get_creation_times(env: &Env, creator: &Address) -> Vec<u64> {
+    let key = (symbol_short!("CREATIONS"), creator);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
 /// Store the contract admin
 pub fn store_admin(env: &Env, admin: &Address) {
     let key = symbol_short!("ADMIN");
@@ -113,3 +812,73 @@ pub fn get_admin(env: &Env) -> Option<Address> {
     let key = symbol_short!("ADMIN");
     env.storage().instance().get(&key)
 }
+
+/// Record the layout version of stored data
+pub fn set_schema_version(env: &Env, version: u32) {
+    env.storage().instance().set(&symbol_short!("SCHEMA"), &version);
+}
+
+/// Record the next group ID a migration batch should start from
+pub fn set_migration_cursor(env: &Env, group_id: u64) {
+    env.storage().instance().set(&symbol_short!("MIGRATED"), &group_id);
+}
+
+/// Get the next group ID a migration batch should start from
+pub fn get_migration_cursor(env: &Env) -> u64 {
+    env.storage().instance().get(&symbol_short!("MIGRATED")).unwrap_or(1)
+}
+
+/// Get the layout version of stored data
+///
+/// Deployments from before versioning have no entry and are on version 1.
+pub fn get_schema_version(env: &Env) -> u32 {
+    env.storage().instance().get(&symbol_short!("SCHEMA")).unwrap_or(1)
+}
+
+/// Set whether the contract is paused
+pub fn set_paused(env: &Env, paused: bool) {
+    let key = symbol_short!("PAUSED");
+    env.storage().instance().set(&key, &paused);
+}
+
+/// Check if the contract is paused
+pub fn is_paused(env: &Env) -> bool {
+    let key = symbol_short!("PAUSED");
+    env.storage().instance().get(&key).unwrap_or(false)
+}
+
+/// Allow or stop the creation of new groups
+pub fn set_creation_enabled(env: &Env, enabled: bool) {
+    let key = symbol_short!("CREATION");
+    env.storage().instance().set(&key, &enabled);
+}
+
+/// Check if new groups may be created
+pub fn is_creation_enabled(env: &Env) -> bool {
+    let key = symbol_short!("CREATION");
+    env.storage().instance().get(&key).unwrap_or(true)
+}
+
+/// Set the platform fee, in basis points, and who collects it
+pub fn set_platform_fee(env: &Env, fee_bps: u32, collector: &Address) {
+    let key = symbol_short!("PLATFEE");
+    env.storage().instance().set(&key, &(fee_bps, collector.clone()));
+}
+
+/// Get the platform fee, in basis points, and who collects it, if one is set
+pub fn get_platform_fee(env: &Env) -> Option<(u32, Address)> {
+    let key = symbol_short!("PLATFEE");
+    env.storage().instance().get(&key)
+}
+
+/// Set or clear the reentrancy lock
+pub fn set_locked(env: &Env, locked: bool) {
+    let key = symbol_short!("LOCK");
+    env.storage().instance().set(&key, &locked);
+}
+
+/// Check if the reentrancy lock is held
+pub fn is_locked(env: &Env) -> bool {
+    let key = symbol_short!("LOCK");
+    env.storage().instance().get(&key).unwrap_or(false)
+}
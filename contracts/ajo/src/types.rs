@@ -1,4 +1,154 @@
-use soroban_sdk::{contracttype, Address, Vec};
+use soroban_sdk::{contracttype, Address, Map, String, Symbol, Vec};
+
+/// How the payout recipient for each cycle is chosen
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PayoutMode {
+    /// Members are paid in a fixed rotation (join order)
+    RotationFixed,
+    
+    /// Members bid a discount each cycle; the highest bidder receives the pot
+    Auction,
+}
+
+/// How strictly a group enforces each cycle's contribution deadline
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContributionPolicy {
+    /// Contributions are rejected once the deadline passes
+    Strict,
+    
+    /// Contributions are accepted for this many seconds past the deadline,
+    /// with the group's late penalty charged on top
+    Grace(u64),
+}
+
+/// Where a group is in its life
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GroupState {
+    /// Waiting for enough members to start the rotation
+    Recruiting,
+    
+    /// The rotation is running
+    Active,
+    
+    /// Every cycle has been paid out
+    Completed,
+    
+    /// The group was cancelled or expired before finishing
+    Cancelled,
+}
+
+/// Optional settings chosen when a group is created
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupConfig {
+    /// How payout recipients are selected
+    pub payout_mode: PayoutMode,
+    
+    /// Timestamp after which an unfilled group can be expired
+    pub fill_deadline: Option<u64>,
+    
+    /// Refundable stake each member deposits when joining (0 for none)
+    pub collateral_amount: i128,
+    
+    /// Number of payout cycles to run; defaults to `max_members`
+    pub total_cycles: Option<u32>,
+    
+    /// Late fee in basis points charged on contributions made in a grace period
+    pub penalty_bps: u32,
+    
+    /// Whether only invited addresses may join
+    pub is_private: bool,
+    
+    /// Whether the creator may kick members who haven't been paid out yet
+    pub allow_forfeiture: bool,
+    
+    /// Creator's cut of each payout in basis points, at most 1000 (10%)
+    pub commission_bps: u32,
+    
+    /// Members needed before the rotation starts; defaults to `max_members`.
+    /// Below `max_members`, late joiners fill the remaining slots mid-run.
+    pub min_members: Option<u32>,
+    
+    /// Whether contributions are accepted after the deadline
+    pub contribution_policy: ContributionPolicy,
+    
+    /// Price oracle that sets each contribution's token amount, if any
+    pub oracle: Option<Address>,
+    
+    /// Value each contribution must be worth, in the oracle's price units;
+    /// required when `oracle` is set
+    pub target_value: i128,
+    
+    /// Only address allowed to trigger payouts; anyone may when `None`
+    pub payout_caller: Option<Address>,
+    
+    /// Whether payouts are rounded down to whole tokens, the dust being
+    /// shared out when the group ends
+    pub round_payouts: bool,
+}
+
+impl Default for GroupConfig {
+    fn default() -> Self {
+        GroupConfig {
+            payout_mode: PayoutMode::RotationFixed,
+            fill_deadline: None,
+            collateral_amount: 0,
+            total_cycles: None,
+            penalty_bps: 0,
+            is_private: false,
+            allow_forfeiture: false,
+            commission_bps: 0,
+            min_members: None,
+            contribution_policy: ContributionPolicy::Strict,
+            oracle: None,
+            target_value: 0,
+            payout_caller: None,
+            round_payouts: false,
+        }
+    }
+}
+
+/// Saved group parameters a creator can reuse
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupTemplate {
+    /// Token contract the groups save in
+    pub token: Address,
+    
+    /// Amount each member contributes per cycle
+    pub contribution_amount: i128,
+    
+    /// Duration of each cycle in seconds
+    pub cycle_duration: u64,
+    
+    /// Maximum number of members allowed in each group
+    pub max_members: u32,
+    
+    /// Optional group settings
+    pub config: GroupConfig,
+}
+
+/// A group as stored before schema version 2
+///
+/// Only read back by `migrate`, which rewrites these entries as `Group`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyGroupV1 {
+    pub id: u64,
+    pub creator: Address,
+    pub contribution_amount: i128,
+    pub cycle_duration: u64,
+    pub max_members: u32,
+    pub members: Vec<Address>,
+    pub current_cycle: u32,
+    pub payout_index: u32,
+    pub created_at: u64,
+    pub cycle_start_time: u64,
+    pub is_complete: bool,
+}
 
 /// Represents an Ajo group configuration and state
 #[contracttype]
@@ -10,6 +160,9 @@ pub struct Group {
     /// Creator of the group
     pub creator: Address,
     
+    /// Token contract contributions and payouts are made in
+    pub token: Address,
+    
     /// Fixed contribution amount per member per cycle (in stroops for XLM)
     pub contribution_amount: i128,
     
@@ -34,18 +187,129 @@ pub struct Group {
     /// Timestamp when the current cycle started
     pub cycle_start_time: u64,
     
+    /// Whether the group has filled up and cycles have begun
+    pub is_started: bool,
+    
+    /// When the first cycle began, once the group has started (unknown for
+    /// groups migrated from schema version 1)
+    pub started_at: Option<u64>,
+    
     /// Whether the group has completed all cycles
     pub is_complete: bool,
+    
+    /// Whether the group was cancelled by its creator before completing
+    pub is_cancelled: bool,
+    
+    /// Whether the creator has temporarily halted contributions and payouts
+    pub is_paused: bool,
+    
+    /// How payout recipients are selected
+    pub payout_mode: PayoutMode,
+    
+    /// Randomized payout order; empty means members are paid in join order
+    pub payout_order: Vec<Address>,
+    
+    /// Timestamp after which the group can be expired if still not full
+    pub fill_deadline: Option<u64>,
+    
+    /// Refundable stake each member deposits when joining, slashed on default
+    pub collateral_amount: i128,
+    
+    /// Number of payout cycles the group runs before completing
+    pub total_cycles: u32,
+    
+    /// Late fee in basis points charged on contributions made in a grace period
+    pub penalty_bps: u32,
+    
+    /// Late fees collected so far, paid at completion to members who never defaulted
+    pub penalty_pool: i128,
+    
+    /// Whether only addresses invited by the creator may join
+    pub is_private: bool,
+    
+    /// Whether kicked members forfeit a payout they haven't received yet
+    pub allow_forfeiture: bool,
+    
+    /// When the final payout went out, once the group has completed
+    pub completed_at: Option<u64>,
+    
+    /// Creator's cut of each payout in basis points
+    pub commission_bps: u32,
+    
+    /// Members needed before the rotation starts
+    pub min_members: u32,
+    
+    /// How late contributions are handled
+    pub contribution_policy: ContributionPolicy,
+    
+    /// Agreed per-cycle amounts for members who don't pay `contribution_amount`
+    pub contribution_by_member: Map<Address, i128>,
+    
+    /// When the most recent payout went out, once there has been one
+    pub last_payout_time: Option<u64>,
+    
+    /// Price oracle that sets each contribution's token amount, if any
+    pub oracle: Option<Address>,
+    
+    /// Value each contribution must be worth, in the oracle's price units
+    pub target_value: i128,
+    
+    /// Only address allowed to trigger payouts; anyone may when `None`
+    pub payout_caller: Option<Address>,
+    
+    /// Whether payouts are rounded down to whole tokens
+    pub round_payouts: bool,
+    
+    /// Fractions of a token held back from rounded payouts so far
+    pub dust_pool: i128,
 }
 
-/// Contribution status for a specific member in a specific cycle
+/// Human-readable description of a group
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ContributionRecord {
+pub struct GroupMetadata {
+    /// Short display name (at most 64 bytes)
+    pub name: String,
+    
+    /// Longer free-form description (at most 256 bytes)
+    pub description: String,
+}
+
+/// A member's objection to the cycle it was raised in
+///
+/// While open, the cycle can't be paid out until the creator resolves it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    /// Member who raised the dispute
     pub member: Address,
-    pub group_id: u64,
-    pub cycle: u32,
-    pub has_paid: bool,
+    
+    /// Short code describing the problem
+    pub reason: Symbol,
+    
+    /// When the dispute was raised
+    pub raised_at: u64,
+}
+
+/// Receipt for a member's contribution to a cycle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContributionRecord {
+    /// Amount paid, including any late fee
+    pub amount: i128,
+    
+    /// When the contribution was recorded
+    pub timestamp: u64,
+}
+
+/// A token price reported by an oracle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    /// Value of one whole token
+    pub price: i128,
+    
+    /// When the price was last updated
     pub timestamp: u64,
 }
 
@@ -100,3 +364,52 @@ pub struct GroupStatus {
     /// Current block timestamp
     pub current_time: u64,
 }
+
+/// A group together with values derived from its current cycle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupView {
+    /// The stored group
+    pub group: Group,
+    
+    /// Payout the current cycle's pool comes to once every member pays
+    pub pool_this_cycle: i128,
+    
+    /// Number of members who have contributed in the current cycle
+    pub contributions_received: u32,
+    
+    /// Member due the current cycle's payout, if any remain
+    pub next_recipient: Option<Address>,
+    
+    /// Seconds left in the current contribution window (0 once it closes)
+    pub seconds_until_deadline: u64,
+}
+
+/// Everything stored about a group, for off-chain snapshots and audits
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupSnapshot {
+    /// The stored group
+    pub group: Group,
+    
+    /// Who has contributed, one row per cycle from cycle 1 to `total_cycles`
+    /// and one column per member in `group.members` order
+    pub contributions: Vec<Vec<bool>>,
+    
+    /// Whether each member, in `group.members` order, has been paid out
+    pub payouts_received: Vec<bool>,
+}
+
+/// Aggregate counts across every group in the contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractStats {
+    /// Groups ever created
+    pub total_groups: u64,
+    
+    /// Groups still running (neither completed nor cancelled)
+    pub active_groups: u64,
+    
+    /// Groups that paid out every cycle
+    pub completed_groups: u64,
+}
@@ -1,6 +1,14 @@
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{contractclient, token, Address, Env, Map, Vec};
 
-use crate::types::Group;
+use crate::types::{ContributionPolicy, Group, PayoutMode, PriceData};
+
+/// The price feed an oracle-priced group reads
+#[allow(dead_code)]
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    /// Latest price of `asset`, if the oracle has one
+    fn lastprice(env: Env, asset: Address) -> Option<PriceData>;
+}
 
 /// Check if an address is a member of the group
 pub fn is_member(members: &Vec<Address>, address: &Address) -> bool {
@@ -22,10 +30,541 @@ pub fn all_members_contributed(env: &Env, group: &Group) -> bool {
     true
 }
 
+/// Let members who asked for reminders know the current cycle is open
+///
+/// Only members who still owe are listed, so those who prepaid the cycle
+/// are left out. Nothing is emitted when nobody needs reminding.
+pub fn remind_members(env: &Env, group: &Group) {
+    let mut owing = Vec::new(env);
+    for member in crate::storage::get_reminders(env, group.id).iter() {
+        if is_member(&group.members, &member)
+            && !crate::storage::has_contributed(env, group.id, group.current_cycle, &member)
+        {
+            owing.push_back(member);
+        }
+    }
+    if !owing.is_empty() {
+        crate::events::emit_reminders(env, group.id, group.current_cycle, &owing);
+    }
+}
+
+/// Check that an address has room to take part in another group
+///
+/// Only running groups count; completed and cancelled ones don't.
+pub fn ensure_below_group_limit(env: &Env, member: &Address) -> Result<(), crate::errors::AjoError> {
+    let active = crate::storage::get_member_groups(env, member)
+        .iter()
+        .filter_map(|group_id| crate::storage::get_group(env, group_id))
+        .filter(|group| !group.is_complete)
+        .count();
+    let active = u32::try_from(active).unwrap_or(u32::MAX);
+    if active >= MAX_ACTIVE_GROUPS {
+        return Err(crate::errors::AjoError::MaxMembersAboveLimit);
+    }
+    Ok(())
+}
+
+/// Check whether anything can be collected for the current cycle
+///
+/// A member counts once they have contributed, once their guarantor can
+/// cover them, or while they have collateral posted.
+pub fn any_member_covered(env: &Env, group: &Group) -> bool {
+    group.members.iter().any(|member| {
+        crate::storage::has_contributed(env, group.id, group.current_cycle, &member)
+            || crate::storage::get_collateral(env, group.id, &member) > 0
+            || crate::storage::get_guarantor(env, group.id, &member).is_some_and(|guarantor| {
+                can_draw_from(env, group, &guarantor, member_contribution(group, &member))
+            })
+    })
+}
+
 /// Calculate the total payout amount for a cycle
-pub fn calculate_payout_amount(group: &Group) -> i128 {
-    let member_count = group.members.len() as i128;
-    group.contribution_amount * member_count
+///
+/// Sums every member's agreed contribution.
+pub fn calculate_payout_amount(group: &Group) -> Result<i128, crate::errors::AjoError> {
+    let mut total: i128 = 0;
+    for member in group.members.iter() {
+        total = total
+            .checked_add(member_contribution(group, &member))
+            .ok_or(crate::errors::AjoError::PayoutOverflow)?;
+    }
+    Ok(total)
+}
+
+/// Amount a member owes each cycle
+///
+/// Their agreed amount if the creator set one, otherwise the group's flat
+/// `contribution_amount`.
+pub fn member_contribution(group: &Group, member: &Address) -> i128 {
+    group
+        .contribution_by_member
+        .get(member.clone())
+        .unwrap_or(group.contribution_amount)
+}
+
+/// Token amount a member owes for a contribution made now
+///
+/// Oracle-priced groups convert `target_value` at the oracle's latest price
+/// for the group's token; other groups charge the member's agreed amount.
+/// A missing or stale price is reported as `InvalidToken`, standing in for
+/// `StalePrice`, and a price so high the target converts to no tokens as
+/// `ContributionAmountZero`.
+pub fn contribution_due(
+    env: &Env,
+    group: &Group,
+    member: &Address,
+) -> Result<i128, crate::errors::AjoError> {
+    let oracle = match &group.oracle {
+        Some(oracle) => oracle,
+        None => return Ok(member_contribution(group, member)),
+    };
+    let now = get_current_timestamp(env);
+    let quote = match PriceOracleClient::new(env, oracle).try_lastprice(&group.token) {
+        Ok(Ok(Some(quote))) => quote,
+        _ => return Err(crate::errors::AjoError::InvalidToken),
+    };
+    if quote.price <= 0 || quote.timestamp.saturating_add(MAX_PRICE_AGE) < now {
+        return Err(crate::errors::AjoError::InvalidToken);
+    }
+    let unit = token_unit(env, &group.token)?;
+    let due = group
+        .target_value
+        .checked_mul(unit)
+        .map(|scaled| scaled / quote.price)
+        .ok_or(crate::errors::AjoError::PayoutOverflow)?;
+    if due <= 0 {
+        return Err(crate::errors::AjoError::ContributionAmountZero);
+    }
+    Ok(due)
+}
+
+/// Smallest units in one whole token
+pub fn token_unit(env: &Env, token: &Address) -> Result<i128, crate::errors::AjoError> {
+    10i128
+        .checked_pow(token::Client::new(env, token).decimals())
+        .ok_or(crate::errors::AjoError::PayoutOverflow)
+}
+
+/// Token amount a member put into a cycle's pool
+///
+/// Oracle-priced contributions vary with the price, so their receipts are
+/// read; every other contribution is the member's agreed amount.
+pub fn contributed_amount(env: &Env, group: &Group, cycle: u32, member: &Address) -> i128 {
+    if group.oracle.is_none() {
+        return member_contribution(group, member);
+    }
+    crate::storage::get_contribution_record(env, group.id, cycle, member)
+        .map(|record| record.amount)
+        .unwrap_or_else(|| member_contribution(group, member))
+}
+
+/// Get the member scheduled for the current rotation slot
+///
+/// Follows the shuffled payout order when one has been set, otherwise join order.
+pub fn scheduled_recipient(group: &Group) -> Option<Address> {
+    if group.payout_order.is_empty() {
+        group.members.get(group.payout_index)
+    } else {
+        group.payout_order.get(group.payout_index)
+    }
+}
+
+/// Get the member who should be paid for the current rotation slot
+///
+/// Normally the scheduled member, but anyone already paid out is skipped in
+/// favour of the next unpaid member in the rotation, so a misconfigured order
+/// can never pay a member twice.
+pub fn next_unpaid_recipient(env: &Env, group: &Group) -> Option<Address> {
+    let order = if group.payout_order.is_empty() {
+        &group.members
+    } else {
+        &group.payout_order
+    };
+    let start = usize::try_from(group.payout_index).unwrap_or(usize::MAX);
+    order
+        .iter()
+        .skip(start)
+        .chain(order.iter().take(start))
+        .chain(group.members.iter())
+        .find(|member| !crate::storage::has_received_payout(env, group.id, member))
+        .or_else(|| scheduled_recipient(group))
+}
+
+/// Number of payout cycles the group runs with its current membership
+///
+/// A group that started below capacity runs one cycle per member, so late
+/// joiners extend the rotation until `total_cycles` is reached.
+pub fn rotation_length(group: &Group) -> u32 {
+    group.total_cycles.min(group.members.len())
+}
+
+/// Check that a token address refers to a deployed token contract
+///
+/// Contract strkeys start with 'C'; account strkeys start with 'G'. The
+/// contract must also answer a cheap token read, so a typo or an address
+/// with nothing deployed is caught before any funds are sent to it.
+///
+/// Returns the token's decimals, from that same read.
+pub fn validate_token(env: &Env, token: &Address) -> Result<u32, crate::errors::AjoError> {
+    let strkey = token.to_string();
+    let mut buf = [0u8; STRKEY_LEN];
+    if usize::try_from(strkey.len()) != Ok(STRKEY_LEN) {
+        return Err(crate::errors::AjoError::InvalidToken);
+    }
+    strkey.copy_into_slice(&mut buf);
+    if buf[0] != b'C' {
+        return Err(crate::errors::AjoError::InvalidToken);
+    }
+    match token::Client::new(env, token).try_decimals() {
+        Ok(Ok(decimals)) => Ok(decimals),
+        _ => Err(crate::errors::AjoError::InvalidToken),
+    }
+}
+
+/// Move `amount` of the group's token from a member into the contract
+pub fn transfer_in(env: &Env, group: &Group, from: &Address, amount: i128) {
+    transfer_token_in(env, &group.token, from, amount);
+}
+
+/// Move `amount` of `token` from a member into the contract
+pub fn transfer_token_in(env: &Env, token: &Address, from: &Address, amount: i128) {
+    token::Client::new(env, token).transfer(from, &env.current_contract_address(), &amount);
+}
+
+/// How much of the group's token `owner` has approved the contract to draw
+pub fn contract_allowance(env: &Env, group: &Group, owner: &Address) -> i128 {
+    token::Client::new(env, &group.token).allowance(owner, &env.current_contract_address())
+}
+
+/// Pull `amount` of the group's token into the contract using the
+/// allowance `from` gave it
+pub fn transfer_in_from_allowance(env: &Env, group: &Group, from: &Address, amount: i128) {
+    let contract = env.current_contract_address();
+    token::Client::new(env, &group.token).transfer_from(&contract, from, &contract, &amount);
+}
+
+/// Check whether both `from`'s balance and their allowance to the contract
+/// cover `amount` of the group's token
+pub fn can_draw_from(env: &Env, group: &Group, from: &Address, amount: i128) -> bool {
+    let balance = token::Client::new(env, &group.token).balance(from);
+    balance >= amount && contract_allowance(env, group, from) >= amount
+}
+
+/// Pull `amount` of the group's token from a guarantor into the contract
+///
+/// Draws on the allowance the guarantor gave the contract. Returns `false`,
+/// moving nothing, if the balance or the allowance doesn't cover it.
+pub fn transfer_from_guarantor(
+    env: &Env,
+    group: &Group,
+    guarantor: &Address,
+    amount: i128,
+) -> bool {
+    if !can_draw_from(env, group, guarantor, amount) {
+        return false;
+    }
+    transfer_in_from_allowance(env, group, guarantor, amount);
+    true
+}
+
+/// Move `amount` of the group's token from the contract to an address
+///
+/// Zero amounts are skipped.
+pub fn transfer_out(env: &Env, group: &Group, to: &Address, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    token::Client::new(env, &group.token).transfer(&env.current_contract_address(), to, &amount);
+}
+
+/// Refund every contribution recorded for cycles that haven't been paid out
+///
+/// Covers the current cycle and any later cycles members prepaid.
+pub fn refund_open_contributions(env: &Env, group: &Group) {
+    for member in group.members.iter() {
+        refund_member_contributions(env, group, &member);
+    }
+}
+
+/// Refund one member's contributions for cycles that haven't been paid out
+pub fn refund_member_contributions(env: &Env, group: &Group, member: &Address) {
+    let last_cycle = group.current_cycle.max(group.total_cycles);
+    let mut refund: i128 = 0;
+    for cycle in group.current_cycle..=last_cycle {
+        if crate::storage::has_contributed(env, group.id, cycle, member) {
+            crate::storage::remove_contribution(env, group.id, cycle, member);
+            refund += contributed_amount(env, group, cycle, member);
+        }
+    }
+    if refund > 0 {
+        transfer_out(env, group, member, refund);
+        crate::events::emit_refund(env, group.id, member, refund);
+    }
+}
+
+/// Take a member's collateral deposit for a group
+pub fn collect_collateral(env: &Env, group: &Group, member: &Address) {
+    if group.collateral_amount > 0 {
+        transfer_in(env, group, member, group.collateral_amount);
+        crate::storage::store_collateral(env, group.id, member, group.collateral_amount);
+        crate::events::emit_collateral_deposited(env, group.id, member, group.collateral_amount);
+    }
+}
+
+/// Report each member's net position at completion
+///
+/// Missed contributions are taken from collateral cycle by cycle as they
+/// happen, so by now any shortfall the collateral could cover is settled.
+/// What remains is reported per member as what they received less what they
+/// paid in.
+pub fn settle_member_balances(env: &Env, group: &Group) {
+    let flows = crate::storage::get_member_flows(env, group.id);
+    for member in group.members.iter() {
+        let (paid_in, received) = flows.get(member.clone()).unwrap_or((0, 0));
+        crate::events::emit_member_settled(env, group.id, &member, received.saturating_sub(paid_in));
+    }
+}
+
+/// Add to what a member has paid into and received from a group's payouts
+pub fn add_member_flow(
+    flows: &mut Map<Address, (i128, i128)>,
+    member: &Address,
+    paid_in: i128,
+    received: i128,
+) {
+    let (total_in, total_out) = flows.get(member.clone()).unwrap_or((0, 0));
+    flows.set(
+        member.clone(),
+        (total_in.saturating_add(paid_in), total_out.saturating_add(received)),
+    );
+}
+
+/// Return whatever collateral is still held for each member of a group
+pub fn refund_collateral(env: &Env, group: &Group) {
+    // Groups without collateral never hold any
+    if group.collateral_amount <= 0 {
+        return;
+    }
+    
+    for member in group.members.iter() {
+        refund_member_collateral(env, group, &member);
+    }
+}
+
+/// Return whatever collateral is still held for one member
+///
+/// Returns the amount refunded, 0 if nothing was held. The deposit record is
+/// cleared first, so a second call refunds nothing.
+pub fn refund_member_collateral(env: &Env, group: &Group, member: &Address) -> i128 {
+    let held = crate::storage::get_collateral(env, group.id, member);
+    if held > 0 {
+        crate::storage::store_collateral(env, group.id, member, 0);
+        transfer_out(env, group, member, held);
+        crate::events::emit_collateral_refunded(env, group.id, member, held);
+    }
+    held.max(0)
+}
+
+/// Late fee owed on one member's contribution
+pub fn calculate_late_penalty(
+    group: &Group,
+    member: &Address,
+) -> Result<i128, crate::errors::AjoError> {
+    member_contribution(group, member)
+        .checked_mul(i128::from(group.penalty_bps))
+        .map(|scaled| scaled / i128::from(BPS_DENOMINATOR))
+        .ok_or(crate::errors::AjoError::PayoutOverflow)
+}
+
+/// Operator's fee on a payout, and the address it goes to
+///
+/// Returns `None` when no platform fee is set.
+pub fn calculate_platform_fee(
+    env: &Env,
+    payout: i128,
+) -> Result<Option<(Address, i128)>, crate::errors::AjoError> {
+    let (fee_bps, collector) = match crate::storage::get_platform_fee(env) {
+        Some((fee_bps, collector)) if fee_bps > 0 => (fee_bps, collector),
+        _ => return Ok(None),
+    };
+    let fee = payout
+        .checked_mul(i128::from(fee_bps))
+        .map(|scaled| scaled / i128::from(BPS_DENOMINATOR))
+        .ok_or(crate::errors::AjoError::PayoutOverflow)?;
+    Ok(Some((collector, fee)))
+}
+
+/// Creator's commission on a payout
+pub fn calculate_commission(group: &Group, payout: i128) -> Result<i128, crate::errors::AjoError> {
+    payout
+        .checked_mul(i128::from(group.commission_bps))
+        .map(|scaled| scaled / i128::from(BPS_DENOMINATOR))
+        .ok_or(crate::errors::AjoError::PayoutOverflow)
+}
+
+/// Share the penalty pool equally among a group's members and empty it
+///
+/// Used when a group ends early, so every member gets an equal cut.
+pub fn distribute_penalty_pool(env: &Env, group: &mut Group) {
+    for (member, amount) in even_shares(env, group.penalty_pool, &group.members).iter() {
+        transfer_out(env, group, &member, amount);
+        crate::events::emit_penalty_share(env, group.id, &member, amount);
+    }
+    group.penalty_pool = 0;
+}
+
+/// Pay the penalty pool to the members who never defaulted and empty it
+///
+/// Members with a default in this group get no bonus. If every member
+/// defaulted, the pool is shared among all of them instead.
+pub fn distribute_completion_bonus(env: &Env, group: &mut Group) {
+    if group.penalty_pool <= 0 {
+        return;
+    }
+    
+    let mut eligible = Vec::new(env);
+    for member in group.members.iter() {
+        if crate::storage::get_group_default_count(env, group.id, &member) == 0 {
+            eligible.push_back(member);
+        }
+    }
+    if eligible.is_empty() {
+        eligible = group.members.clone();
+    }
+    
+    for (member, amount) in even_shares(env, group.penalty_pool, &eligible).iter() {
+        transfer_out(env, group, &member, amount);
+        crate::events::emit_completion_bonus(env, group.id, &member, amount);
+    }
+    group.penalty_pool = 0;
+}
+
+/// Pay referrers for the members they brought in, out of the penalty and
+/// dust pools
+///
+/// Each referred member still in the group earns their referrer
+/// `REFERRAL_BONUS_BPS` of that member's contribution, taken from the
+/// penalty pool first and then the dust pool, for as long as the pools
+/// last. Referrers who have since left earn nothing.
+pub fn pay_referral_bonuses(env: &Env, group: &mut Group) {
+    if group.penalty_pool <= 0 && group.dust_pool <= 0 {
+        return;
+    }
+    
+    for member in group.members.clone().iter() {
+        let referrer = match crate::storage::get_referral(env, group.id, &member) {
+            Some(referrer) if is_member(&group.members, &referrer) => referrer,
+            _ => continue,
+        };
+        let bonus = member_contribution(group, &member)
+            .saturating_mul(i128::from(REFERRAL_BONUS_BPS))
+            / i128::from(BPS_DENOMINATOR);
+        let from_penalties = bonus.min(group.penalty_pool).max(0);
+        let from_dust = (bonus - from_penalties).min(group.dust_pool).max(0);
+        let amount = from_penalties + from_dust;
+        if amount == 0 {
+            continue;
+        }
+        group.penalty_pool -= from_penalties;
+        group.dust_pool -= from_dust;
+        transfer_out(env, group, &referrer, amount);
+        crate::events::emit_referral_bonus(env, group.id, &referrer, &member, amount);
+    }
+}
+
+/// Share the dust held back from rounded payouts equally among a group's
+/// members and empty it
+pub fn distribute_dust(env: &Env, group: &mut Group) {
+    for (member, amount) in even_shares(env, group.dust_pool, &group.members).iter() {
+        transfer_out(env, group, &member, amount);
+        crate::events::emit_dust_share(env, group.id, &member, amount);
+    }
+    group.dust_pool = 0;
+}
+
+/// Divide `total` into `count` equal whole shares
+///
+/// Returns each share and what is left over, so that share * count +
+/// remainder is always exactly `total`. Callers decide who gets the
+/// remainder; it must never just be dropped. A count below 1 leaves it all
+/// as remainder.
+pub fn split_evenly(total: i128, count: i128) -> (i128, i128) {
+    if count < 1 {
+        return (0, total);
+    }
+    (total / count, total % count)
+}
+
+/// Split a pool of `total` equally among `recipients`
+///
+/// Any indivisible remainder is handed out one unit at a time in order, and
+/// recipients whose share rounds to nothing are left out.
+fn even_shares(env: &Env, total: i128, recipients: &Vec<Address>) -> Vec<(Address, i128)> {
+    let mut shares = Vec::new(env);
+    let recipient_count = i128::from(recipients.len());
+    if total <= 0 || recipient_count == 0 {
+        return shares;
+    }
+    
+    let (share, remainder) = split_evenly(total, recipient_count);
+    for (index, recipient) in (0i128..).zip(recipients.iter()) {
+        let amount = share + if index < remainder { 1 } else { 0 };
+        if amount > 0 {
+            shares.push_back((recipient, amount));
+        }
+    }
+    shares
+}
+
+/// Select who receives the payout for the current cycle
+///
+/// Returns the recipient and the winning bid (always 0 outside auction mode).
+/// A fixed rotation pays the next member who hasn't been paid yet. In auction
+/// mode the highest bid from a member who hasn't been paid wins, ties going to
+/// the earlier member; with no bids the earliest unpaid member wins.
+pub fn select_payout_recipient(env: &Env, group: &Group) -> Option<(Address, i128)> {
+    match group.payout_mode {
+        PayoutMode::RotationFixed => next_unpaid_recipient(env, group).map(|m| (m, 0)),
+        PayoutMode::Auction => {
+            let mut winner: Option<(Address, i128)> = None;
+            for member in group.members.iter() {
+                if crate::storage::has_received_payout(env, group.id, &member) {
+                    continue;
+                }
+                let bid = crate::storage::get_bid(env, group.id, group.current_cycle, &member);
+                match &winner {
+                    Some((_, best)) if bid <= *best => {}
+                    _ => winner = Some((member, bid)),
+                }
+            }
+            winner
+        }
+    }
+}
+
+/// Fail with `ContractPaused` while the admin has paused the contract
+pub fn ensure_not_paused(env: &Env) -> Result<(), crate::errors::AjoError> {
+    if crate::storage::is_paused(env) {
+        return Err(crate::errors::AjoError::ContractPaused);
+    }
+    Ok(())
+}
+
+/// Take the reentrancy lock, failing with `Reentrancy` if it's already held
+///
+/// A failed call rolls back its storage writes, so the lock only needs to be
+/// released on the success path.
+pub fn acquire_lock(env: &Env) -> Result<(), crate::errors::AjoError> {
+    if crate::storage::is_locked(env) {
+        return Err(crate::errors::AjoError::Reentrancy);
+    }
+    crate::storage::set_locked(env, true);
+    Ok(())
+}
+
+/// Release the reentrancy lock
+pub fn release_lock(env: &Env) {
+    crate::storage::set_locked(env, false);
 }
 
 /// Get the current timestamp
@@ -33,13 +572,153 @@ pub fn get_current_timestamp(env: &Env) -> u64 {
     env.ledger().timestamp()
 }
 
+/// When a group last saw activity
+///
+/// This is the later of the last change to the group and the most recent
+/// contribution to its current cycle.
+pub fn last_activity(env: &Env, group: &Group) -> u64 {
+    let mut last = crate::storage::get_last_activity(env, group.id).unwrap_or(group.created_at);
+    for member in group.members.iter() {
+        let record =
+            crate::storage::get_contribution_record(env, group.id, group.current_cycle, &member);
+        if let Some(record) = record {
+            last = last.max(record.timestamp);
+        }
+    }
+    last
+}
+
+/// Time after which a group with no further activity counts as abandoned
+///
+/// This is `ABANDON_TIMEOUT` after the last activity, stretched to two full
+/// cycles for groups whose cycles are longer than that.
+pub fn abandoned_at(env: &Env, group: &Group) -> u64 {
+    let timeout = ABANDON_TIMEOUT.max(group.cycle_duration.saturating_mul(2));
+    last_activity(env, group).saturating_add(timeout)
+}
+
+/// Check that a group's membership is consistent
+///
+/// While the group is running its creator must be a member, and no address
+/// may appear in `members` more than once.
+pub fn check_group_invariants(group: &Group) -> Result<(), crate::errors::AjoError> {
+    let running = !group.is_complete && !group.is_cancelled;
+    if running && !group.members.contains(&group.creator) {
+        return Err(crate::errors::AjoError::NotMember);
+    }
+    
+    for (index, member) in (0u32..).zip(group.members.iter()) {
+        if group.members.first_index_of(&member) != Some(index) {
+            return Err(crate::errors::AjoError::AlreadyMember);
+        }
+    }
+    Ok(())
+}
+
+/// Take a member out of a group and shrink its rotation to match
+///
+/// A paid member's slot is already behind the payout index, while an unpaid
+/// member's slot is forfeited. Groups taking late joiners keep their cycle
+/// count, as the freed slot can be refilled.
+pub fn remove_from_rotation(env: &Env, group: &mut Group, member: &Address, paid_out: bool) {
+    let slot_reopens = group.min_members < group.max_members;
+    if paid_out {
+        group.payout_index -= 1;
+        if !slot_reopens {
+            group.total_cycles -= 1;
+        }
+    } else if !slot_reopens {
+        group.total_cycles = group.total_cycles.min(group.members.len() - 1);
+    }
+    
+    if let Some(index) = group.members.first_index_of(member) {
+        group.members.remove(index);
+    }
+    group.contribution_by_member.remove(member.clone());
+    if let Some(position) = group.payout_order.first_index_of(member) {
+        group.payout_order.remove(position);
+    }
+    crate::storage::remove_member_group(env, member, group.id);
+    crate::storage::add_departed_member(env, group.id, member);
+}
+
+/// Count the groups an address created at or after `window_start`
+pub fn creations_by(env: &Env, creator: &Address, window_start: u64) -> u32 {
+    crate::storage::get_creation_times(env, creator)
+        .iter()
+        .filter(|created_at| *created_at >= window_start)
+        .count()
+        .try_into()
+        .unwrap_or(u32::MAX)
+}
+
+/// Record a group creation, forgetting ones from before `window_start`
+pub fn record_creation(env: &Env, creator: &Address, now: u64, window_start: u64) {
+    let mut recent = Vec::new(env);
+    for created_at in crate::storage::get_creation_times(env, creator).iter() {
+        if created_at >= window_start {
+            recent.push_back(created_at);
+        }
+    }
+    recent.push_back(now);
+    crate::storage::store_creation_times(env, creator, &recent);
+}
+
+/// Version of this contract build, bumped with each release
+pub const CONTRACT_VERSION: u32 = 1;
+
+/// Most groups one address may create within `CREATION_WINDOW`
+pub const MAX_CREATIONS_PER_WINDOW: u32 = 5;
+
+/// Rolling window group creations are counted over, in seconds (one day)
+pub const CREATION_WINDOW: u64 = 86_400;
+
+/// Bonus a referrer earns for each referred member who stays to the end,
+/// in basis points of that member's contribution (1%)
+pub const REFERRAL_BONUS_BPS: u32 = 100;
+
+/// Upper limit on group size, keeping per-member loops within budget
+pub const MAX_MEMBERS_LIMIT: u32 = 100;
+
+/// Most groups a single batch query may ask for, or a page may scan
+pub const MAX_BATCH_IDS: u32 = 50;
+
+/// Most running groups one address may belong to at once
+pub const MAX_ACTIVE_GROUPS: u32 = 20;
+
+/// Basis points in 100%
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Highest creator commission, in basis points (10%)
+pub const MAX_COMMISSION_BPS: u32 = 1_000;
+
+/// Highest platform fee, in basis points (5%)
+pub const MAX_PLATFORM_FEE_BPS: u32 = 500;
+
+/// Length of a Stellar strkey address
+pub const STRKEY_LEN: usize = 56;
+
+/// Longest allowed group name, in bytes
+pub const MAX_NAME_LEN: u32 = 64;
+
+/// Longest allowed group description, in bytes
+pub const MAX_DESCRIPTION_LEN: u32 = 256;
+
+/// Longest allowed cycle, in seconds (ten years)
+pub const MAX_CYCLE_DURATION: u64 = 10 * 365 * 24 * 60 * 60;
+
+/// Oldest oracle price a contribution may be priced at, in seconds (an hour)
+pub const MAX_PRICE_AGE: u64 = 60 * 60;
+
+/// Time without activity after which a group counts as abandoned (a year)
+pub const ABANDON_TIMEOUT: u64 = 365 * 24 * 60 * 60;
+
 /// Logic to ensure group parameters are sane before creating it.
 pub fn validate_group_params(
     amount: i128,
     duration: u64,
     max_members: u32,
 ) -> Result<(), crate::errors::AjoError> {
-    const MAX_MEMBERS_LIMIT: u32 = 100;
     
     // Amounts must be positive
     if amount == 0 {
@@ -53,6 +732,11 @@ pub fn validate_group_params(
         return Err(crate::errors::AjoError::CycleDurationZero);
     }
     
+    // Nor one that would never come round
+    if duration > MAX_CYCLE_DURATION {
+        return Err(crate::errors::AjoError::CycleDurationTooLong);
+    }
+    
     // We need at least two people to rotate money
     if max_members < 2 {
         return Err(crate::errors::AjoError::MaxMembersBelowMinimum);
@@ -63,13 +747,18 @@ pub fn validate_group_params(
         return Err(crate::errors::AjoError::MaxMembersAboveLimit);
     }
     
+    // A full group's pool must fit in an i128
+    if amount.checked_mul(i128::from(max_members)).is_none() {
+        return Err(crate::errors::AjoError::PayoutOverflow);
+    }
+    
     Ok(())
 }
 
 /// Get cycle start and end timestamps
-pub fn get_cycle_window(group: &Group, current_time: u64) -> (u64, u64) {
+pub fn get_cycle_window(group: &Group, _current_time: u64) -> (u64, u64) {
     let cycle_start = group.cycle_start_time;
-    let cycle_end = cycle_start + group.cycle_duration;
+    let cycle_end = cycle_start.saturating_add(group.cycle_duration);
     (cycle_start, cycle_end)
 }
 
@@ -79,3 +768,41 @@ pub fn is_within_cycle_window(group: &Group, current_time: u64) -> bool {
     current_time >= cycle_start && current_time < cycle_end
 }
 
+/// Check if a late contribution still falls in the group's grace period
+pub fn is_within_grace_period(group: &Group, current_time: u64) -> bool {
+    let (_, cycle_end) = get_cycle_window(group, current_time);
+    match group.contribution_policy {
+        ContributionPolicy::Strict => false,
+        ContributionPolicy::Grace(grace) => current_time < cycle_end.saturating_add(grace),
+    }
+}
+
+/// Check if the current cycle's contribution window has closed
+///
+/// Under a grace policy the window stays open until the grace period ends,
+/// since late contributions are still accepted until then.
+pub fn is_cycle_expired(group: &Group, current_time: u64) -> bool {
+    let (_, cycle_end) = get_cycle_window(group, current_time);
+    let grace = match group.contribution_policy {
+        ContributionPolicy::Strict => 0,
+        ContributionPolicy::Grace(grace) => grace,
+    };
+    current_time >= cycle_end.saturating_add(grace)
+}
+
+/// Check if a cycle duration has yet to pass since the group's last payout
+///
+/// Keeps underfunded cycles from being paid out back to back.
+pub fn is_payout_too_soon(group: &Group, current_time: u64) -> bool {
+    match group.last_payout_time {
+        Some(last) => current_time.saturating_sub(last) < group.cycle_duration,
+        None => false,
+    }
+}
+
+/// Seconds left until the current cycle's contribution deadline (0 once passed)
+pub fn time_until_deadline(env: &Env, group: &Group) -> u64 {
+    let now = get_current_timestamp(env);
+    let (_, cycle_end) = get_cycle_window(group, now);
+    cycle_end.saturating_sub(now)
+}